@@ -0,0 +1,141 @@
+use std::sync::{
+    Arc,
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::board::*;
+use crate::brain::*;
+use crate::grid::*;
+
+// Anything able to play a side : the heuristic search, an external engine, a random mover or a scripted bot.
+pub trait Agent: Send {
+    fn name(&self) -> String;
+
+    // None stands for a resignation, also given when the agent has no legal play or is cancelled.
+    fn choose_move(&mut self, board: &BoardState, limits: &AgentLimits) -> Option<(usize, usize)>;
+}
+
+// Agents shared between the game and the threads searching for them.
+pub type SharedAgent = Arc<Mutex<Box<dyn Agent>>>;
+
+// Limits are only hints, an agent ignores the ones it has no use for.
+#[derive(Default)]
+pub struct AgentLimits<'a> {
+    pub move_time: Option<Duration>,
+    pub cancel: Option<&'a AtomicBool>,
+    // Receives the continuation the agent expects, for the agents able to tell.
+    pub progress: Option<&'a Mutex<Option<SearchInfo>>>,
+}
+
+pub struct RandomAgent {
+    rng: StdRng,
+}
+
+// Plays the given moves in order, then lets the fallback agent play, resigning when there is none.
+// A scripted move which is not legal in the position resigns the game.
+pub struct ScriptedAgent {
+    moves: Vec<(usize, usize)>,
+    next_move: usize,
+    fallback: Option<Box<dyn Agent>>,
+}
+
+pub fn share_agent<A: Agent + 'static>(agent: A) -> SharedAgent {
+    Arc::new(Mutex::new(Box::new(agent)))
+}
+
+impl<'a> AgentLimits<'a> {
+    pub fn is_cancelled(&self) -> bool {
+        match self.cancel {
+            Some(cancel) => cancel.load(Ordering::Relaxed),
+            None => false,
+        }
+    }
+
+    pub fn report(&self, info: &SearchInfo) {
+        if let Some(progress) = self.progress {
+            *progress.lock().unwrap() = Some(info.clone());
+        }
+    }
+}
+
+impl Agent for AiConfig {
+    fn name(&self) -> String {
+        self.to_string()
+    }
+
+    fn choose_move(&mut self, board: &BoardState, limits: &AgentLimits) -> Option<(usize, usize)> {
+        self.search_with_info(board, limits.cancel, |info| limits.report(info))
+    }
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> RandomAgent {
+        RandomAgent {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn name(&self) -> String {
+        String::from("random")
+    }
+
+    fn choose_move(&mut self, board: &BoardState, _: &AgentLimits) -> Option<(usize, usize)> {
+        Brain::find_all_plays(board, board.current_player).choose(&mut self.rng).copied()
+    }
+}
+
+impl ScriptedAgent {
+    pub fn new(moves: Vec<(usize, usize)>, fallback: Option<Box<dyn Agent>>) -> ScriptedAgent {
+        ScriptedAgent {
+            moves,
+            next_move: 0,
+            fallback,
+        }
+    }
+
+    // Moves are named as in the engine protocol, "E6-F5".
+    pub fn from_names(names: &[&str], fallback: Option<Box<dyn Agent>>) -> Result<ScriptedAgent, String> {
+        let mut moves = Vec::new();
+        for name in names {
+            match Grid::get_play_from_name(name) {
+                Some(play) => moves.push(play),
+                None => return Err(format!("invalid move {}", name)),
+            }
+        }
+
+        Ok(ScriptedAgent::new(moves, fallback))
+    }
+}
+
+impl Agent for ScriptedAgent {
+    fn name(&self) -> String {
+        match &self.fallback {
+            Some(fallback) => format!("script then {}", fallback.name()),
+            None => String::from("script"),
+        }
+    }
+
+    fn choose_move(&mut self, board: &BoardState, limits: &AgentLimits) -> Option<(usize, usize)> {
+        if self.next_move >= self.moves.len() {
+            return match &mut self.fallback {
+                Some(fallback) => fallback.choose_move(board, limits),
+                None => None,
+            };
+        }
+
+        let play = self.moves[self.next_move];
+        self.next_move += 1;
+        match Brain::find_all_plays(board, board.current_player).contains(&play) {
+            true => Some(play),
+            false => None,
+        }
+    }
+}
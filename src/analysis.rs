@@ -0,0 +1,189 @@
+use std::sync::{
+    Arc,
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, Receiver},
+};
+use std::thread;
+
+use crate::game::*;
+use crate::board::*;
+use crate::brain::*;
+use crate::evaluation::*;
+use crate::game_record::*;
+
+pub const ANALYSIS_DEPTH: u32 = 3;
+// Candidate moves listed when studying a single position.
+pub const CANDIDATE_COUNT: usize = 5;
+pub const CANDIDATE_DEPTH: u32 = 7;
+
+pub const INACCURACY_LOSS: i32 = 50;
+pub const MISTAKE_LOSS: i32 = 150;
+pub const BLUNDER_LOSS: i32 = 300;
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub enum MoveQuality {
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+pub struct MoveAnalysis {
+    pub player: PlayerSide,
+    pub played: (usize, usize),
+    pub played_score: i32,
+    pub best: (usize, usize),
+    pub best_score: i32,
+    pub quality: MoveQuality,
+}
+
+impl MoveQuality {
+    pub fn from_score_loss(score_loss: i32) -> MoveQuality {
+        if score_loss >= BLUNDER_LOSS {
+            MoveQuality::Blunder
+        }
+        else if score_loss >= MISTAKE_LOSS {
+            MoveQuality::Mistake
+        }
+        else if score_loss >= INACCURACY_LOSS {
+            MoveQuality::Inaccuracy
+        }
+        else {
+            MoveQuality::Good
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MoveQuality::Good => "good",
+            MoveQuality::Inaccuracy => "inaccuracy",
+            MoveQuality::Mistake => "mistake",
+            MoveQuality::Blunder => "blunder",
+        }
+    }
+}
+
+impl MoveAnalysis {
+    pub fn get_score_loss(&self) -> i32 {
+        self.best_score - self.played_score
+    }
+}
+
+// Scores are given from the point of view of the player of the move.
+pub fn analyse_move(board: &BoardState, played: (usize, usize), depth: u32, context: &mut SearchContext) -> MoveAnalysis {
+    let scores = Brain::score_plays(board, depth, context);
+    let played_score = match scores.iter().find(|score| score.0 == played) {
+        Some(score) => score.1,
+        None => panic!("{:?} is not a legal play", played),
+    };
+
+    let mut best = (played, played_score);
+    for score in &scores {
+        if score.1 > best.1 {
+            best = *score;
+        }
+    }
+
+    MoveAnalysis {
+        player: board.current_player,
+        played,
+        played_score,
+        best: best.0,
+        best_score: best.1,
+        quality: MoveQuality::from_score_loss(best.1 - played_score),
+    }
+}
+
+// Analyses the moves of a game one after the other on a worker thread.
+// Dropping the handle stops the analysis.
+pub struct AnalysisHandle {
+    receiver: Receiver<MoveAnalysis>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl AnalysisHandle {
+    pub fn start(record: &GameRecord, weights: EvaluationWeights) -> AnalysisHandle {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let states = record.states.clone();
+        let moves = record.moves.clone();
+
+        thread::spawn(move || {
+            let mut context = SearchContext::new(&weights, Some(&thread_cancel));
+            for (board, played) in states.iter().zip(moves.iter()) {
+                let analysis = analyse_move(board, *played, ANALYSIS_DEPTH, &mut context);
+                if context.is_cancelled() || sender.send(analysis).is_err() {
+                    return;
+                }
+            }
+        });
+
+        AnalysisHandle {
+            receiver,
+            cancel,
+        }
+    }
+
+    pub fn poll(&self) -> Vec<MoveAnalysis> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for AnalysisHandle {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+// Searches the best plays of a position on a worker thread, the lines of the last completed depth being kept.
+// Dropping the handle stops the search.
+pub struct CandidatesHandle {
+    lines: Arc<Mutex<Vec<SearchInfo>>>,
+    done: Arc<AtomicBool>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl CandidatesHandle {
+    pub fn start(board: BoardState, config: AiConfig, count: usize) -> CandidatesHandle {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let thread_lines = lines.clone();
+        let done = Arc::new(AtomicBool::new(false));
+        let thread_done = done.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+
+        thread::spawn(move || {
+            config.search_lines(&board, count, Some(&thread_cancel), |lines| {
+                *thread_lines.lock().unwrap() = lines.to_vec();
+            });
+
+            thread_done.store(true, Ordering::Relaxed);
+        });
+
+        CandidatesHandle {
+            lines,
+            done,
+            cancel,
+        }
+    }
+
+    pub fn get_lines(&self) -> Vec<SearchInfo> {
+        self.lines.lock().unwrap().clone()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for CandidatesHandle {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,182 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::evaluation::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: bench [--depth 6] [--movegen-iterations 20000] [--threads 1] [--no-quiescence] [--output bench.json] [--compare bench.json]";
+
+// Positions from the start to the end of a game, in board notation.
+const BENCH_POSITIONS: [(&str, &str); 5] = [
+    ("start", "...ttt.../....t..../........./...b.b.../..b.. b"),
+    ("opening", "...ttt.../.....t.../.....b.../.....b.../..b.. t"),
+    ("midgame", "...ttt.../........./.....t.../.....b.../..b.. b"),
+    ("endgame", "...tt..t./........./.....b.../........./..b.. b"),
+    ("late endgame", "....t..../........./.......b./........./..... t"),
+];
+
+#[derive(Serialize, Deserialize)]
+#[derive(Debug)]
+struct BenchResult {
+    name: String,
+    depth: u32,
+    best_play: String,
+    score: i32,
+    nodes: u64,
+    time_ms: f64,
+    nodes_per_second: u64,
+    branching_factor: f64,
+    effective_branching_factor: f64,
+    first_play_cutoff_rate: f64,
+    movegen_plays_per_second: u64,
+}
+
+// Generates and plays every play of the position over and over.
+fn bench_move_generation(board: &BoardState, iterations: u32) -> u64 {
+    let start = Instant::now();
+    let mut plays_count: u64 = 0;
+    let mut checksum = 0;
+    for _ in 0..iterations {
+        for play in Brain::find_all_plays(board, board.current_player) {
+            let next_board = board.make_move(play.0, play.1);
+            checksum += next_board.top_pawns.count + next_board.bottom_pawns.count;
+            plays_count += 1;
+        }
+    }
+
+    // Keeps the loop from being optimised away.
+    if checksum == usize::MAX {
+        println!("{}", checksum);
+    }
+
+    (plays_count as f64 / start.elapsed().as_secs_f64().max(1e-6)) as u64
+}
+
+fn bench_position(name: &str, board: &BoardState, depth: u32, threads: usize, quiescence: bool, movegen_iterations: u32) -> BenchResult {
+    let weights = EvaluationWeights::default();
+    let mut context = SearchContext::new(&weights, None);
+    context.threads = threads;
+    context.deterministic = true;
+    context.quiescence = quiescence;
+
+    // Nodes searched by each depth, the effective branching factor being the growth between the last two.
+    let mut depth_nodes = Vec::new();
+    let mut previous_nodes = 0;
+    let result = Brain::iterative_deepening(board, &SearchLimits::depth(depth), &mut context, |info| {
+        depth_nodes.push(info.nodes - previous_nodes);
+        previous_nodes = info.nodes;
+    });
+
+    let info = match result {
+        Some(info) => info,
+        None => panic!("No legal play in the {} position", name),
+    };
+
+    let effective_branching_factor = match depth_nodes.len() {
+        0 | 1 => 0.,
+        length => depth_nodes[length - 1] as f64 / depth_nodes[length - 2].max(1) as f64,
+    };
+
+    BenchResult {
+        name: name.to_owned(),
+        depth: info.depth,
+        best_play: Grid::get_play_name(info.pv[0]),
+        score: info.score,
+        nodes: info.nodes,
+        time_ms: info.elapsed.as_secs_f64() * 1000.,
+        nodes_per_second: info.nodes_per_second(),
+        branching_factor: info.stats.branching_factor(),
+        effective_branching_factor,
+        first_play_cutoff_rate: info.stats.first_play_cutoff_rate(),
+        movegen_plays_per_second: bench_move_generation(board, movegen_iterations),
+    }
+}
+
+fn compare(results: &[BenchResult], path: &str) {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(error) => panic!("{0} : {1}", path, error),
+    };
+
+    let previous: Vec<BenchResult> = match serde_json::from_str(&json) {
+        Ok(previous) => previous,
+        Err(error) => panic!("{0} : {1}", path, error),
+    };
+
+    println!();
+    println!("Compared to {} :", path);
+    println!("position                nodes          nps      movegen  best play");
+    for result in results {
+        let before = match previous.iter().find(|before| before.name == result.name && before.depth == result.depth) {
+            Some(before) => before,
+            None => {
+                println!("{:<14} not in the previous run at depth {}", result.name, result.depth);
+                continue;
+            },
+        };
+
+        let ratio = |now: u64, then: u64| format!("{:+.1}%", (now as f64 / then.max(1) as f64 - 1.) * 100.);
+        let best_play = match before.best_play == result.best_play {
+            true => String::from("same"),
+            false => format!("{0} -> {1}", before.best_play, result.best_play),
+        };
+
+        println!("{:<14} {:>14} {:>12} {:>12}  {}", result.name, format!("{:+}", result.nodes as i64 - before.nodes as i64),
+            ratio(result.nodes_per_second, before.nodes_per_second), ratio(result.movegen_plays_per_second, before.movegen_plays_per_second), best_play);
+    }
+}
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let depth: u32 = command_line.get_or("depth", 6);
+    let movegen_iterations: u32 = command_line.get_or("movegen-iterations", 20000);
+    let threads: usize = command_line.get_or("threads", 1);
+    let quiescence = !command_line.has_flag("no-quiescence");
+
+    // bf : plays per expanded node, ebf : growth of the nodes from one depth to the next, cut1 : cutoffs from the first play.
+    println!("position       depth      nodes    time ms        nps      score     bf    ebf   cut1    movegen/s  best play");
+
+    let mut results = Vec::new();
+    for (name, notation) in BENCH_POSITIONS {
+        let board = match BoardState::from_notation(notation) {
+            Ok(board) => board,
+            Err(error) => panic!("{0} : {1}", name, error),
+        };
+
+        let result = bench_position(name, &board, depth, threads, quiescence, movegen_iterations);
+        println!("{:<14} {:>5} {:>10} {:>10.1} {:>10} {:>10} {:>6.2} {:>6.2} {:>6.2} {:>12}  {}",
+            result.name, result.depth, result.nodes, result.time_ms, result.nodes_per_second, result.score, result.branching_factor,
+            result.effective_branching_factor, result.first_play_cutoff_rate, result.movegen_plays_per_second, result.best_play);
+        results.push(result);
+    }
+
+    let nodes: u64 = results.iter().map(|result| result.nodes).sum();
+    let time_ms: f64 = results.iter().map(|result| result.time_ms).sum();
+    println!("Total : {0} nodes in {1:.1}ms, {2} nps", nodes, time_ms, (nodes as f64 / (time_ms / 1000.).max(1e-6)) as u64);
+
+    if let Some(path) = command_line.get_str("compare") {
+        compare(&results, path);
+    }
+
+    if let Some(path) = command_line.get_str("output") {
+        let json = match serde_json::to_string_pretty(&results) {
+            Ok(json) => json,
+            Err(error) => panic!("{}", error),
+        };
+
+        if let Err(error) = std::fs::write(path, json) {
+            panic!("{0} : {1}", path, error);
+        }
+
+        println!("Results written to {}", path);
+    }
+}
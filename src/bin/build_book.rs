@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::evaluation::*;
+use octo_chess::opening_book::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: build_book [--plies 6] [--depth 4] [--margin 40] [--candidates 3] [--profile balanced] \
+[--output resources/opening_book.json]";
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let profiles = match EvaluationProfile::load_profiles_from_file() {
+        Ok(profiles) => profiles,
+        Err(error) => panic!("{}", error),
+    };
+
+    let profile_name = command_line.get_str("profile").unwrap_or("balanced");
+    let profile = match profiles.iter().find(|profile| profile.name == profile_name) {
+        Some(profile) => profile.clone(),
+        None => panic!("Unknown evaluation profile {}", profile_name),
+    };
+
+    let plies: u32 = command_line.get_or("plies", 6);
+    let depth: u32 = command_line.get_or("depth", 4);
+    let margin: i32 = command_line.get_or("margin", 40);
+    let candidates: usize = command_line.get_or("candidates", 3);
+    let output = match command_line.get_str("output") {
+        Some(output) => std::path::PathBuf::from(output),
+        None => {
+            let mut path = get_resource_dir();
+            path.push(OPENING_BOOK_PATH.trim_start_matches('/'));
+            path
+        }
+    };
+
+    let mut book = OpeningBook::default();
+    let mut visited = HashSet::new();
+    let mut frontier = vec![BoardState::starting_position()];
+    let mut context = SearchContext::new(&profile.weights, None);
+
+    // Every position reached by a book move is searched in turn, keeping the moves scoring close to the best one,
+    // with a weight decreasing with their distance to the best score.
+    for ply in 0..plies {
+        let mut next_frontier = Vec::new();
+        for board in &frontier {
+            if !visited.insert(board.to_notation()) {
+                continue;
+            }
+
+            let mut scores = Brain::score_plays(board, depth, &mut context);
+            scores.sort_by(|left, right| left.1.cmp(&right.1).reverse());
+            let best_score = match scores.first() {
+                Some(score) => score.1,
+                None => continue,
+            };
+
+            for (play, score) in scores.into_iter().take(candidates) {
+                let loss = best_score - score;
+                if loss > margin {
+                    break;
+                }
+
+                book.add_move(board, play, (margin - loss + 1) as u32);
+                let next_board = board.make_move(play.0, play.1);
+                if next_board.get_winner().is_none() {
+                    next_frontier.push(next_board);
+                }
+            }
+        }
+
+        println!("Ply {0}/{1} : {2} positions in the book", ply + 1, plies, book.positions.len());
+        frontier = next_frontier;
+    }
+
+    let json = match serde_json::to_string_pretty(&book) {
+        Ok(json) => json,
+        Err(error) => panic!("{}", error),
+    };
+
+    if let Err(error) = std::fs::write(&output, json) {
+        panic!("{0:?} : {1}", output, error);
+    }
+
+    println!("Opening book written to {:?}", output);
+}
@@ -0,0 +1,66 @@
+use std::time::Instant;
+
+use octo_chess::game::*;
+use octo_chess::tablebase::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: build_tablebase [--max-pawns 2] [--output resources/tablebases]";
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let max_pawns: usize = command_line.get_or("max-pawns", DEFAULT_TABLEBASE_PAWNS);
+    if max_pawns == 0 || max_pawns > MAX_PAWN_NUMBER {
+        panic!("The number of pawns must be between 1 and {}", MAX_PAWN_NUMBER);
+    }
+
+    let output = match command_line.get_str("output") {
+        Some(output) => std::path::PathBuf::from(output),
+        None => {
+            let mut path = get_resource_dir();
+            path.push(TABLEBASE_DIR.trim_start_matches('/'));
+            path
+        }
+    };
+
+    // A capture leads to a table with one pawn less, those are generated first.
+    let mut materials = Vec::new();
+    for top_count in 1..=max_pawns {
+        for bottom_count in 1..=max_pawns {
+            materials.push((top_count, bottom_count));
+        }
+    }
+
+    materials.sort_by_key(|(top_count, bottom_count)| top_count + bottom_count);
+
+    let mut tablebase = Tablebase::default();
+    for (top_count, bottom_count) in materials {
+        let start = Instant::now();
+        println!("{0} against {1} : {2} positions", top_count, bottom_count, Table::get_size(top_count, bottom_count));
+        let table = Table::generate(top_count, bottom_count, &tablebase, |plies, solved| {
+            if solved > 0 {
+                println!("  {0} plies : {1} positions", plies, solved);
+            }
+        });
+
+        let table = match table {
+            Ok(table) => table,
+            Err(error) => panic!("{}", error),
+        };
+
+        let (wins, losses, draws, longest_win) = table.get_summary();
+        println!("  {0} wins, {1} losses, {2} draws for the side to move, longest win in {3} plies, {4:.1}s",
+            wins, losses, draws, longest_win, start.elapsed().as_secs_f64());
+        tablebase.add_table(table);
+    }
+
+    if let Err(error) = tablebase.save_to_dir(&output) {
+        panic!("{}", error);
+    }
+
+    println!("Tablebase written to {:?}", output);
+}
@@ -0,0 +1,120 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::symmetry::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: check_board [--games 1000] [--max-plies 200] [--seed 0]";
+
+fn get_pawn_counts(board: &BoardState) -> (usize, usize) {
+    match board.current_player {
+        PlayerSide::Top => (board.top_pawns.count, board.bottom_pawns.count),
+        PlayerSide::Bottom => (board.bottom_pawns.count, board.top_pawns.count),
+    }
+}
+
+// Properties of a single play, board being the position it is played from.
+fn check_play(board: &BoardState, play: (usize, usize)) -> Result<BoardState, String> {
+    if !board.get_possible_moves(play.1).contains(&play.0) {
+        return Err(String::from("the pawn cannot move back to its tile"));
+    }
+
+    let next_board = board.make_move(play.0, play.1);
+    next_board.check_invariants()?;
+
+    if next_board.current_player != board.current_player.reverse() {
+        return Err(String::from("the side to move did not change"));
+    }
+
+    let is_capture = board.tiles[play.1].is_some();
+    let (my_count, their_count) = get_pawn_counts(board);
+    let (their_next_count, my_next_count) = get_pawn_counts(&next_board);
+    if my_next_count != my_count || their_next_count + is_capture as usize != their_count {
+        return Err(format!("pawn counts went from {0}/{1} to {2}/{3}", my_count, their_count, my_next_count, their_next_count));
+    }
+
+    match (next_board.tiles[play.0], next_board.tiles[play.1]) {
+        (None, Some(pawn)) if pawn.player == board.current_player => {},
+        _ => return Err(String::from("the pawn is not on its new tile")),
+    }
+
+    // The symmetric positions have the symmetric plays and share the same canonical position.
+    let mut plays: Vec<(usize, usize)> = Brain::find_all_plays(&next_board, next_board.current_player);
+    plays.sort_unstable();
+    let canonical_key = next_board.canonical().0.get_key();
+    for transform in BoardTransform::all() {
+        let transformed_board = next_board.transform(transform);
+        let mut transformed_plays: Vec<(usize, usize)> = Brain::find_all_plays(&transformed_board, transformed_board.current_player).into_iter()
+            .map(|play| transform.inverse().apply_to_play(play))
+            .collect();
+        transformed_plays.sort_unstable();
+        if transformed_plays != plays {
+            return Err(format!("the plays of {0} do not match the ones of its transform {1:?}", next_board.to_notation(), transform));
+        }
+
+        if transformed_board.transform(transform).get_key() != next_board.get_key() || transformed_board.canonical().0.get_key() != canonical_key {
+            return Err(format!("the transform {0:?} of {1} is not symmetric", transform, next_board.to_notation()));
+        }
+    }
+
+    match BoardState::from_notation(&next_board.to_notation()) {
+        Ok(parsed) if parsed.to_notation() == next_board.to_notation() => {},
+        _ => return Err(format!("the notation {} does not give back the position", next_board.to_notation())),
+    }
+
+    Ok(next_board)
+}
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let games: u32 = command_line.get_or("games", 1000);
+    let max_plies: u32 = command_line.get_or("max-plies", 200);
+    let seed: u64 = command_line.get_or("seed", 0);
+
+    let mut plies_checked = 0;
+    for game_index in 0..games {
+        // Every game has its own seed so that a failing game can be replayed alone.
+        let game_seed = seed + game_index as u64;
+        let mut rng = StdRng::seed_from_u64(game_seed);
+        let mut board = BoardState::starting_position();
+        let mut moves = Vec::new();
+        if let Err(error) = board.check_invariants() {
+            panic!("Starting position : {}", error);
+        }
+
+        for _ in 0..max_plies {
+            if board.get_winner().is_some() {
+                break;
+            }
+
+            let play = match Brain::find_all_plays(&board, board.current_player).choose(&mut rng) {
+                Some(play) => *play,
+                None => break,
+            };
+
+            moves.push(Grid::get_play_name(play));
+            board = match check_play(&board, play) {
+                Ok(next_board) => next_board,
+                Err(error) => {
+                    println!("Game seed {0}, after {1} : {2}", game_seed, moves.join(" "), error);
+                    println!("Position before the last play : {}", board.to_notation());
+                    std::process::exit(1);
+                },
+            };
+
+            plies_checked += 1;
+        }
+    }
+
+    println!("{0} games, {1} plies checked", games, plies_checked);
+}
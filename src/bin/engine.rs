@@ -0,0 +1,328 @@
+use std::io::{self, BufRead, Write};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::evaluation::*;
+use octo_chess::tablebase::*;
+
+// Line-based protocol read on stdin, answers are written on stdout :
+//   uci                                        -> id name / id author / uciok
+//   isready                                    -> readyok
+//   ucinewgame                                 resets the position to the starting one
+//   setoption name profile value <name>        selects the evaluation profile
+//   setoption name threads value <count>       number of threads of the search
+//   setoption name deterministic value <bool>  whether several threads give the same result as a single one at fixed depth
+//   setoption name quiescence value <bool>     whether the captures are searched past the depth
+//   setoption name multipv value <count>       number of best plays reported, each with its own info line
+//   setoption name weight <name> value <N>     changes a weight of the profile, 0 switching its term off
+//   position startpos [moves E6-F5 ...]
+//   position notation <tiles> <side> [moves E6-F5 ...]
+//   go [depth N] [movetime MS] [infinite]      -> info depth D score S nodes N nps N time MS pv ... / bestmove E6-F5
+//                                                 with multipv above 1 : info depth D multipv I score S ... for each play
+//   stop                                       stops the current search, which still answers its best move
+//   d                                          prints the notation of the current position
+//   eval                                       prints the share of each term in the evaluation of the current position
+//   quit
+
+const DEFAULT_DEPTH: u32 = 6;
+
+struct Engine {
+    board: BoardState,
+    profiles: Vec<EvaluationProfile>,
+    profile: EvaluationProfile,
+    threads: usize,
+    deterministic: bool,
+    quiescence: bool,
+    multi_pv: usize,
+    // Probed by the search when found in the resources.
+    tablebase: Option<Arc<Tablebase>>,
+    search: Option<(JoinHandle<()>, Arc<AtomicBool>)>,
+}
+
+fn send(line: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}", line);
+    let _ = stdout.flush();
+}
+
+fn format_pv(pv: &[(usize, usize)]) -> String {
+    pv.iter().map(|play| Grid::get_play_name(*play)).collect::<Vec<_>>().join(" ")
+}
+
+impl Engine {
+    fn new(profiles: Vec<EvaluationProfile>, tablebase: Option<Arc<Tablebase>>) -> Engine {
+        let profile = match profiles.iter().find(|profile| profile.name == "balanced") {
+            Some(profile) => profile.clone(),
+            None => EvaluationProfile::default(),
+        };
+
+        Engine {
+            board: BoardState::starting_position(),
+            profiles,
+            profile,
+            threads: 1,
+            deterministic: false,
+            quiescence: true,
+            multi_pv: 1,
+            tablebase,
+            search: None,
+        }
+    }
+
+    fn stop_search(&mut self) {
+        if let Some((thread, cancel)) = self.search.take() {
+            cancel.store(true, Ordering::Relaxed);
+            let _ = thread.join();
+        }
+    }
+
+    fn set_option(&mut self, tokens: &[&str]) -> Result<(), String> {
+        match tokens {
+            ["name", "profile", "value", name] => {
+                match self.profiles.iter().find(|profile| profile.name == *name) {
+                    Some(profile) => self.profile = profile.clone(),
+                    None => return Err(format!("unknown profile {}", name)),
+                }
+            },
+            ["name", "threads", "value", count] => {
+                match count.parse::<usize>() {
+                    Ok(count) if count > 0 => self.threads = count,
+                    _ => return Err(format!("invalid thread count {}", count)),
+                }
+            },
+            ["name", "deterministic", "value", value] => {
+                match value.parse::<bool>() {
+                    Ok(value) => self.deterministic = value,
+                    Err(_) => return Err(format!("invalid boolean {}", value)),
+                }
+            },
+            ["name", "quiescence", "value", value] => {
+                match value.parse::<bool>() {
+                    Ok(value) => self.quiescence = value,
+                    Err(_) => return Err(format!("invalid boolean {}", value)),
+                }
+            },
+            ["name", "multipv", "value", count] => {
+                match count.parse::<usize>() {
+                    Ok(count) if count > 0 => self.multi_pv = count,
+                    _ => return Err(format!("invalid line count {}", count)),
+                }
+            },
+            ["name", "weight", name, "value", value] => {
+                match value.parse::<i32>() {
+                    Ok(value) => self.profile.weights.set_weight(name, value)?,
+                    Err(_) => return Err(format!("invalid weight {}", value)),
+                }
+            },
+            _ => return Err(format!("unknown option {}", tokens.join(" "))),
+        }
+
+        Ok(())
+    }
+
+    fn set_position(&mut self, tokens: &[&str]) -> Result<(), String> {
+        let (mut board, rest) = match tokens {
+            ["startpos", rest @ ..] => (BoardState::starting_position(), rest),
+            ["notation", tiles, side, rest @ ..] => (BoardState::from_notation(&format!("{} {}", tiles, side))?, rest),
+            _ => return Err(String::from("expected startpos or notation")),
+        };
+
+        let moves = match rest {
+            [] => &rest[0..0],
+            ["moves", moves @ ..] => moves,
+            _ => return Err(format!("unexpected {}", rest.join(" "))),
+        };
+
+        for name in moves {
+            let play = match Grid::get_play_from_name(name) {
+                Some(play) => play,
+                None => return Err(format!("invalid move {}", name)),
+            };
+
+            if board.get_winner().is_some() || !Brain::find_all_plays(&board, board.current_player).contains(&play) {
+                return Err(format!("illegal move {}", name));
+            }
+
+            board = board.make_move(play.0, play.1);
+        }
+
+        self.board = board;
+        Ok(())
+    }
+
+    fn go(&mut self, tokens: &[&str]) -> Result<(), String> {
+        let mut depth: Option<u32> = None;
+        let mut move_time: Option<Duration> = None;
+        let mut tokens = tokens.iter();
+        while let Some(token) = tokens.next() {
+            let mut get_value = || -> Result<u64, String> {
+                match tokens.next().map(|value| value.parse::<u64>()) {
+                    Some(Ok(value)) => Ok(value),
+                    _ => Err(format!("expected a number after {}", token)),
+                }
+            };
+
+            match *token {
+                "depth" => depth = Some(get_value()? as u32),
+                "movetime" => move_time = Some(Duration::from_millis(get_value()?)),
+                "infinite" => depth = Some(MAX_SEARCH_DEPTH),
+                _ => return Err(format!("unknown go parameter {}", token)),
+            }
+        }
+
+        // A search limited by time only goes as deep as it can.
+        let limits = SearchLimits {
+            depth: match (depth, move_time) {
+                (Some(depth), _) => depth,
+                (None, Some(_)) => MAX_SEARCH_DEPTH,
+                (None, None) => DEFAULT_DEPTH,
+            },
+            move_time,
+        };
+
+        let board = self.board;
+        let weights = self.profile.weights;
+        let threads = self.threads;
+        let deterministic = self.deterministic;
+        let quiescence = self.quiescence;
+        let multi_pv = self.multi_pv;
+        let tablebase = self.tablebase.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let thread = thread::spawn(move || {
+            let mut context = SearchContext::new(&weights, Some(&thread_cancel));
+            context.threads = threads;
+            context.deterministic = deterministic;
+            context.quiescence = quiescence;
+            context.tablebase = tablebase.as_deref();
+            let best_play = match multi_pv {
+                1 => {
+                    let result = Brain::iterative_deepening(&board, &limits, &mut context, |info| {
+                        send(&format!("info depth {0} score {1} nodes {2} nps {3} time {4} pv {5}",
+                            info.depth, info.score, info.nodes, info.nodes_per_second(), info.elapsed.as_millis(), format_pv(&info.pv)));
+                    });
+
+                    result.and_then(|info| info.pv.first().copied())
+                },
+                _ => {
+                    let lines = Brain::search_multi_pv(&board, &limits, multi_pv, &mut context, |lines| {
+                        for (index, info) in lines.iter().enumerate() {
+                            send(&format!("info depth {0} multipv {1} score {2} nodes {3} nps {4} time {5} pv {6}",
+                                info.depth, index + 1, info.score, info.nodes, info.nodes_per_second(), info.elapsed.as_millis(), format_pv(&info.pv)));
+                        }
+                    });
+
+                    // As with a single line, any legal play is better than none if not even the first depth could be completed.
+                    match lines.first() {
+                        Some(info) => info.pv.first().copied(),
+                        None => Brain::find_all_plays(&board, board.current_player).first().copied(),
+                    }
+                },
+            };
+
+            match best_play {
+                Some(play) => send(&format!("bestmove {}", Grid::get_play_name(play))),
+                None => send("bestmove none"),
+            }
+        });
+
+        self.search = Some((thread, cancel));
+        Ok(())
+    }
+
+    // Returns false once the engine should exit.
+    fn handle_command(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (command, arguments) = match tokens.split_first() {
+            Some((command, arguments)) => (*command, arguments),
+            None => return true,
+        };
+
+        // Only isready and stop can be handled while searching, any other command waits for the search to be stopped.
+        match command {
+            "isready" => {
+                send("readyok");
+                return true;
+            },
+            "stop" | "quit" => self.stop_search(),
+            _ => {
+                if let Some((thread, _)) = self.search.take() {
+                    let _ = thread.join();
+                }
+            },
+        }
+
+        let result = match command {
+            "uci" => {
+                send("id name octo_chess");
+                send("id author AntonMakesGames");
+                send("option name profile type string default balanced");
+                send("option name threads type spin default 1 min 1");
+                send("option name deterministic type check default false");
+                send("option name quiescence type check default true");
+                send("option name multipv type spin default 1 min 1");
+                send("uciok");
+                Ok(())
+            },
+            "ucinewgame" => {
+                self.board = BoardState::starting_position();
+                Ok(())
+            },
+            "setoption" => self.set_option(arguments),
+            "position" => self.set_position(arguments),
+            "go" => self.go(arguments),
+            "d" => {
+                send(&self.board.to_notation());
+                Ok(())
+            },
+            "eval" => {
+                for line in Brain::evaluate_breakdown(&self.board, &self.profile.weights).to_string().lines() {
+                    send(line);
+                }
+
+                Ok(())
+            },
+            "stop" => Ok(()),
+            "quit" => return false,
+            _ => Err(format!("unknown command {}", command)),
+        };
+
+        if let Err(error) = result {
+            send(&format!("info string error : {}", error));
+        }
+
+        true
+    }
+}
+
+fn main() {
+    let profiles = match EvaluationProfile::load_profiles_from_file() {
+        Ok(profiles) => profiles,
+        Err(error) => panic!("{}", error),
+    };
+
+    let tablebase = Tablebase::load_from_file().ok().map(Arc::new);
+    let mut engine = Engine::new(profiles, tablebase);
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+
+        if !engine.handle_command(&line) {
+            return;
+        }
+    }
+
+    // The input has been closed, a running search is still allowed to answer.
+    if let Some((thread, _)) = engine.search.take() {
+        let _ = thread.join();
+    }
+}
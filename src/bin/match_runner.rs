@@ -0,0 +1,201 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::agent::*;
+use octo_chess::external_engine::*;
+use octo_chess::evaluation::*;
+use octo_chess::opening_book::*;
+use octo_chess::tablebase::*;
+use octo_chess::self_play::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: match_runner [--a-algorithm greedy|alphabeta|random] [--a-depth N] [--a-profile balanced] [--a-book] [--a-tablebase] \
+[--a-margin N] [--a-threads 1] [--a-quiescence true] [--a-engine \"<executable> [arguments]\"] [--a-movetime MS] [--b-algorithm greedy|alphabeta|random] \
+[--b-depth N] [--b-profile balanced] [--b-book] [--b-tablebase] [--b-margin N] [--b-threads 1] [--b-quiescence true] [--b-engine \"<executable> [arguments]\"] [--b-movetime MS] \
+[--games 100] [--opening-plies 4] [--max-plies 200] [--seed 0]";
+
+// The player of a side, from which a new agent is made for every game.
+enum Contender {
+    Search(AiConfig),
+    Random,
+    Engine(ExternalEngineConfig),
+}
+
+fn get_contender(command_line: &CommandLine, prefix: &str, profiles: &[EvaluationProfile]) -> Contender {
+    if let Some(engine) = command_line.get_str(&format!("{}-engine", prefix)) {
+        let move_time = Duration::from_millis(command_line.get_or(&format!("{}-movetime", prefix), DEFAULT_ENGINE_MOVE_TIME));
+        return match ExternalEngineConfig::new(engine, move_time) {
+            Some(config) => Contender::Engine(config),
+            None => panic!("Empty engine command line"),
+        };
+    }
+
+    let algorithm_name = command_line.get_str(&format!("{}-algorithm", prefix)).unwrap_or("greedy");
+    if algorithm_name == "random" {
+        return Contender::Random;
+    }
+
+    let algorithm = match SearchAlgorithm::from_name(algorithm_name) {
+        Some(algorithm) => algorithm,
+        None => panic!("Unknown search algorithm {}", algorithm_name),
+    };
+
+    let profile_name = command_line.get_str(&format!("{}-profile", prefix)).unwrap_or("balanced");
+    let profile = match profiles.iter().find(|profile| profile.name == profile_name) {
+        Some(profile) => profile.clone(),
+        None => panic!("Unknown evaluation profile {}", profile_name),
+    };
+
+    let mut opening_book = None;
+    if command_line.has_flag(&format!("{}-book", prefix)) {
+        match OpeningBook::load_from_file() {
+            Ok(book) => opening_book = Some(Arc::new(book)),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    let mut tablebase = None;
+    if command_line.has_flag(&format!("{}-tablebase", prefix)) {
+        match Tablebase::load_from_file() {
+            Ok(loaded) => tablebase = Some(Arc::new(loaded)),
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    Contender::Search(AiConfig {
+        algorithm,
+        depth: command_line.get_or(&format!("{}-depth", prefix), algorithm.default_depth()),
+        profile,
+        opening_book,
+        tablebase,
+        seed: None,
+        random_margin: command_line.get_or(&format!("{}-margin", prefix), DEFAULT_RANDOM_MARGIN),
+        threads: command_line.get_or(&format!("{}-threads", prefix), 1),
+        deterministic: true,
+        quiescence: command_line.get_or(&format!("{}-quiescence", prefix), true),
+    })
+}
+
+impl Contender {
+    // With a margin, the search plays randomly among its best plays with a different seed for every game.
+    fn get_agent(&self, command_line: &CommandLine, prefix: &str, game_seed: u64) -> Box<dyn Agent> {
+        match self {
+            Contender::Search(config) => {
+                let mut config = config.clone();
+                if command_line.get_str(&format!("{}-margin", prefix)).is_some() {
+                    config.seed = Some(game_seed);
+                }
+
+                Box::new(config)
+            },
+            Contender::Random => Box::new(RandomAgent::new(game_seed)),
+            Contender::Engine(config) => Box::new(ExternalEngineAgent::new(config.clone())),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Contender::Search(config) => config.to_string(),
+            Contender::Random => String::from("random"),
+            Contender::Engine(config) => format!("{0} {1}", config.command, config.arguments.join(" ")),
+        }
+    }
+}
+
+fn format_elo(elo: f64) -> String {
+    if elo.is_finite() {
+        format!("{:+.0}", elo)
+    }
+    else if elo > 0. {
+        String::from("+inf")
+    }
+    else {
+        String::from("-inf")
+    }
+}
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let profiles = match EvaluationProfile::load_profiles_from_file() {
+        Ok(profiles) => profiles,
+        Err(error) => panic!("{}", error),
+    };
+
+    let contender_a = get_contender(&command_line, "a", &profiles);
+    let contender_b = get_contender(&command_line, "b", &profiles);
+    let games: u32 = command_line.get_or("games", 100);
+    let opening_plies: u32 = command_line.get_or("opening-plies", 4);
+    let max_plies: usize = command_line.get_or("max-plies", MAX_GAME_PLIES);
+    let seed: u64 = command_line.get_or("seed", 0);
+
+    println!("A : {}", contender_a.name());
+    println!("B : {}", contender_b.name());
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut score = MatchScore::new();
+    let mut a_time = std::time::Duration::ZERO;
+    let mut a_moves = 0;
+    let mut b_time = std::time::Duration::ZERO;
+    let mut b_moves = 0;
+    let mut opening = BoardState::starting_position();
+
+    // Each opening is played twice so that both engines get to play it from both sides.
+    for game_index in 0..games {
+        let a_side = if game_index % 2 == 0 { PlayerSide::Bottom } else { PlayerSide::Top };
+        if game_index % 2 == 0 {
+            opening = play_random_opening(&BoardState::starting_position(), opening_plies, &mut rng);
+        }
+
+        let game_seed = seed.wrapping_add(game_index as u64);
+        let mut agent_a = contender_a.get_agent(&command_line, "a", game_seed);
+        let mut agent_b = contender_b.get_agent(&command_line, "b", game_seed);
+        let summary = match a_side {
+            PlayerSide::Top => play_game(&opening, agent_a.as_mut(), agent_b.as_mut(), max_plies),
+            PlayerSide::Bottom => play_game(&opening, agent_b.as_mut(), agent_a.as_mut(), max_plies),
+        };
+
+        score.record(summary.outcome, a_side);
+        match a_side {
+            PlayerSide::Top => {
+                a_time += summary.top_time;
+                a_moves += summary.top_moves;
+                b_time += summary.bottom_time;
+                b_moves += summary.bottom_moves;
+            },
+            PlayerSide::Bottom => {
+                a_time += summary.bottom_time;
+                a_moves += summary.bottom_moves;
+                b_time += summary.top_time;
+                b_moves += summary.top_moves;
+            },
+        }
+
+        let result = match summary.outcome {
+            GameOutcome::Winner(winner) if winner == a_side => "A wins",
+            GameOutcome::Winner(_) => "B wins",
+            GameOutcome::Draw => "draw",
+        };
+
+        println!("Game {0}/{1} : A as {2:?}, {3} in {4} plies  (A {5} - {6} - {7} B)", game_index + 1, games, a_side, result, summary.plies, score.wins, score.draws, score.losses);
+    }
+
+    let (elo, elo_low, elo_high) = score.get_elo();
+    println!();
+    println!("Games : {0}, A wins : {1}, draws : {2}, B wins : {3}", score.games(), score.wins, score.draws, score.losses);
+    println!("Score of A : {0:.1}/{1}", score.points(), score.games());
+    println!("Elo difference A - B : {0} [{1}, {2}] (95%)", format_elo(elo), format_elo(elo_low), format_elo(elo_high));
+    println!("Average move time : A {0:.2}ms, B {1:.2}ms",
+        a_time.as_secs_f64() * 1000. / a_moves.max(1) as f64,
+        b_time.as_secs_f64() * 1000. / b_moves.max(1) as f64);
+}
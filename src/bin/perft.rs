@@ -0,0 +1,43 @@
+use std::time::Instant;
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: perft [--depth 5] [--position \"<notation>\"] [--divide]";
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let board = match command_line.get_str("position") {
+        Some(notation) => match BoardState::from_notation(notation) {
+            Ok(board) => board,
+            Err(error) => panic!("{0} : {1}", notation, error),
+        },
+        None => BoardState::starting_position(),
+    };
+
+    let depth: u32 = command_line.get_or("depth", 5);
+    if command_line.has_flag("divide") {
+        let mut total = 0;
+        for (play, count) in Brain::perft_divide(&board, depth) {
+            println!("{0} : {1}", Grid::get_play_name(play), count);
+            total += count;
+        }
+
+        println!("Total : {}", total);
+        return;
+    }
+
+    for depth in 1..=depth {
+        let start = Instant::now();
+        let count = Brain::perft(&board, depth);
+        let elapsed = start.elapsed().as_secs_f64();
+        println!("depth {0} : {1} positions in {2:.3}s, {3} per second", depth, count, elapsed, (count as f64 / elapsed.max(1e-6)) as u64);
+    }
+}
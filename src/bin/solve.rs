@@ -0,0 +1,54 @@
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::grid::*;
+use octo_chess::solver::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: solve [--size 2] [--position \"<notation>\"] [--depth 40] [--max-entries 4194304]";
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let reduced_board = match ReducedBoard::new(command_line.get_or("size", 2)) {
+        Ok(reduced_board) => reduced_board,
+        Err(error) => panic!("{}", error),
+    };
+
+    // Positions are given in the notation of the full grid.
+    let position = match command_line.get_str("position") {
+        Some(notation) => match BoardState::from_notation(notation) {
+            Ok(position) => position,
+            Err(error) => panic!("{0} : {1}", notation, error),
+        },
+        None => reduced_board.starting_position(),
+    };
+
+    let max_depth: u32 = command_line.get_or("depth", DEFAULT_SOLVER_DEPTH);
+    println!("Solving {0} on the board of size {1} ({2} tiles)", position.to_notation(), reduced_board.size, reduced_board.get_tile_count());
+
+    let mut solver = Solver::new(reduced_board);
+    solver.max_entries = command_line.get_or("max-entries", DEFAULT_MAX_ENTRIES);
+    let result = solver.solve(&position, max_depth, |progress| {
+        let state = match progress.completed { true => "done", false => "..." };
+        println!("depth {0:>3} {1:>4} : {2} nodes, {3} positions stored, {4:.1}s",
+            progress.depth, state, progress.nodes, progress.entries, progress.elapsed.as_secs_f64());
+    });
+
+    let (result, line) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("{}", error),
+    };
+
+    let side = match position.current_player { PlayerSide::Top => "Top", PlayerSide::Bottom => "Bottom" };
+    match result {
+        SolverResult::Unknown => println!("No forced result within {} plies", max_depth),
+        result => {
+            println!("{0} to move : {1}", side, result);
+            println!("Proof line : {}", line.iter().map(|play| Grid::get_play_name(*play)).collect::<Vec<_>>().join(" "));
+        },
+    }
+}
@@ -0,0 +1,37 @@
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::evaluation::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: tactics --position \"<notation>\" [--depth 1]";
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    let notation = match command_line.get_str("position") {
+        Some(notation) if !command_line.has_flag("help") => notation,
+        _ => {
+            println!("{}", USAGE);
+            return;
+        }
+    };
+
+    let board = match BoardState::from_notation(notation) {
+        Ok(board) => board,
+        Err(error) => panic!("{0} : {1}", notation, error),
+    };
+
+    let depth: u32 = command_line.get_or("depth", 1);
+    let weights = EvaluationWeights::default();
+    for quiescence in [false, true] {
+        let mut context = SearchContext::new(&weights, None);
+        context.quiescence = quiescence;
+        let play = match Brain::search_alpha_beta(&board, depth, &mut context) {
+            Some(play) => Grid::get_play_name(play),
+            None => String::from("none"),
+        };
+
+        let label = match quiescence { true => "with quiescence", false => "without quiescence" };
+        println!("{0} : {1}, {2} nodes of which {3} in the quiescence search", label, play, context.nodes, context.stats.quiescence_nodes);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt::Write as _;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::evaluation::*;
+use octo_chess::brain::*;
+use octo_chess::self_play::*;
+use octo_chess::cli::*;
+
+const USAGE: &str = "Usage: tune [--base balanced] [--iterations 200] [--pairs 4] [--algorithm greedy|alphabeta] [--depth 2] [--opening-plies 4] \
+[--perturbation 0.1] [--learning-rate 1.0] [--validation-pairs 50] [--seed 0] [--name tuned] \
+[--output tuned_profile.json] [--report tuned_profile_report.txt]";
+
+struct TuningSettings {
+    pairs: u32,
+    algorithm: SearchAlgorithm,
+    depth: u32,
+    opening_plies: u32,
+}
+
+impl TuningSettings {
+    fn get_config(&self, weights: &EvaluationWeights) -> AiConfig {
+        AiConfig {
+            algorithm: self.algorithm,
+            depth: self.depth,
+            profile: EvaluationProfile {
+                name: String::new(),
+                weights: *weights,
+            },
+            opening_book: None,
+            tablebase: None,
+            seed: None,
+            random_margin: DEFAULT_RANDOM_MARGIN,
+            threads: 1,
+            deterministic: true,
+            quiescence: true,
+        }
+    }
+}
+
+// Plays each random opening twice, swapping sides, so neither weight set benefits from the opening.
+fn play_pairs<R: Rng>(candidate: &EvaluationWeights, opponent: &EvaluationWeights, pairs: u32, settings: &TuningSettings, rng: &mut R) -> MatchScore {
+    let mut candidate = settings.get_config(candidate);
+    let mut opponent = settings.get_config(opponent);
+    let mut score = MatchScore::new();
+    for _ in 0..pairs {
+        let opening = play_random_opening(&BoardState::starting_position(), settings.opening_plies, rng);
+
+        let summary = play_game(&opening, &mut candidate, &mut opponent, MAX_GAME_PLIES);
+        score.record(summary.outcome, PlayerSide::Top);
+
+        let summary = play_game(&opening, &mut opponent, &mut candidate, MAX_GAME_PLIES);
+        score.record(summary.outcome, PlayerSide::Bottom);
+    }
+
+    score
+}
+
+fn to_weights(base: &EvaluationWeights, theta: &[f64]) -> EvaluationWeights {
+    let mut values = [0; EvaluationWeights::TUNABLE_NAMES.len()];
+    for index in 0..values.len() {
+        values[index] = theta[index].round().max(0.) as i32;
+    }
+
+    base.with_tunable(values)
+}
+
+fn main() {
+    let command_line = CommandLine::from_env();
+    if command_line.has_flag("help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let profiles = match EvaluationProfile::load_profiles_from_file() {
+        Ok(profiles) => profiles,
+        Err(error) => panic!("{}", error),
+    };
+
+    let base_name = command_line.get_str("base").unwrap_or("balanced");
+    let base = match profiles.iter().find(|profile| profile.name == base_name) {
+        Some(profile) => profile.clone(),
+        None => panic!("Unknown evaluation profile {}", base_name),
+    };
+
+    let iterations: u32 = command_line.get_or("iterations", 200);
+    let perturbation: f64 = command_line.get_or("perturbation", 0.1);
+    let learning_rate: f64 = command_line.get_or("learning-rate", 1.);
+    let validation_pairs: u32 = command_line.get_or("validation-pairs", 50);
+    let seed: u64 = command_line.get_or("seed", 0);
+    let name = command_line.get_str("name").unwrap_or("tuned").to_owned();
+    let output = command_line.get_str("output").unwrap_or("tuned_profile.json").to_owned();
+    let report_path = command_line.get_str("report").unwrap_or("tuned_profile_report.txt").to_owned();
+
+    let algorithm_name = command_line.get_str("algorithm").unwrap_or("alphabeta");
+    let algorithm = match SearchAlgorithm::from_name(algorithm_name) {
+        Some(algorithm) => algorithm,
+        None => panic!("Unknown search algorithm {}", algorithm_name),
+    };
+
+    let settings = TuningSettings {
+        pairs: command_line.get_or("pairs", 4_u32).max(1),
+        algorithm,
+        depth: command_line.get_or("depth", 2),
+        opening_plies: command_line.get_or("opening-plies", 4),
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let base_weights = base.weights;
+    let mut theta: Vec<f64> = base_weights.get_tunable().iter().map(|value| *value as f64).collect();
+    let steps: Vec<f64> = theta.iter().map(|value| (value.abs() * perturbation).max(1.)).collect();
+
+    // SPSA : every parameter is perturbed at once in a random direction, and the match result between
+    // the two perturbed weight sets is used as the gradient estimate along that direction.
+    for iteration in 0..iterations {
+        let gain = learning_rate / (1. + iteration as f64 / (iterations as f64 * 0.1).max(1.)).powf(0.602);
+        let directions: Vec<f64> = theta.iter().map(|_| if rng.gen::<bool>() { 1. } else { -1. }).collect();
+
+        let plus: Vec<f64> = (0..theta.len()).map(|index| theta[index] + steps[index] * directions[index]).collect();
+        let minus: Vec<f64> = (0..theta.len()).map(|index| theta[index] - steps[index] * directions[index]).collect();
+
+        let score = play_pairs(&to_weights(&base_weights, &plus), &to_weights(&base_weights, &minus), settings.pairs, &settings, &mut rng);
+        let result = (score.points() * 2. - score.games() as f64) / score.games() as f64;
+
+        for index in 0..theta.len() {
+            theta[index] = (theta[index] + gain * steps[index] * result * directions[index]).max(0.);
+        }
+
+        println!("Iteration {0}/{1} : plus scored {2}/{3}, weights {4:?}", iteration + 1, iterations, score.points(), score.games(), to_weights(&base_weights, &theta).get_tunable());
+    }
+
+    let tuned = EvaluationProfile {
+        name,
+        weights: to_weights(&base_weights, &theta),
+    };
+
+    let validation = play_pairs(&tuned.weights, &base_weights, validation_pairs, &settings, &mut rng);
+
+    let mut report = String::new();
+    writeln!(report, "Tuning of '{0}' into '{1}'", base.name, tuned.name).unwrap();
+    writeln!(report, "Iterations {0}, pairs per iteration {1}, {2} depth {3}, opening plies {4}, seed {5}", iterations, settings.pairs, settings.algorithm.name(), settings.depth, settings.opening_plies, seed).unwrap();
+    writeln!(report).unwrap();
+    for (index, parameter) in EvaluationWeights::TUNABLE_NAMES.iter().enumerate() {
+        writeln!(report, "{0:>18} : {1:>6} -> {2:>6}", parameter, base_weights.get_tunable()[index], tuned.weights.get_tunable()[index]).unwrap();
+    }
+
+    writeln!(report).unwrap();
+    let games = validation.games().max(1) as f64;
+    writeln!(report, "Against baseline over {0} games : {1} wins ({2:.1}%), {3} draws ({4:.1}%), {5} losses ({6:.1}%)",
+        validation.games(),
+        validation.wins, validation.wins as f64 * 100. / games,
+        validation.draws, validation.draws as f64 * 100. / games,
+        validation.losses, validation.losses as f64 * 100. / games).unwrap();
+
+    print!("\n{}", report);
+
+    let profile_json = match serde_json::to_string_pretty(&tuned) {
+        Ok(json) => json,
+        Err(error) => panic!("{}", error),
+    };
+
+    if let Err(error) = std::fs::write(&output, profile_json) {
+        panic!("{0} : {1}", output, error);
+    }
+
+    if let Err(error) = std::fs::write(&report_path, report) {
+        panic!("{0} : {1}", report_path, error);
+    }
+
+    println!("Profile written to {0}, report written to {1}", output, report_path);
+}
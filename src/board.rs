@@ -1,6 +1,5 @@
 use crate::{
     pawn::*,
-    tiles::*,
     grid::*,
     game::*,
 };
@@ -15,6 +14,156 @@ pub struct BoardState {
 }
 
 impl BoardState {
+    pub fn empty() -> BoardState {
+        BoardState {
+            tiles: [Option::None; NUMBER_OF_TILES],
+            current_player: PlayerSide::Bottom,
+            top_pawns: PawnArray::new(),
+            bottom_pawns: PawnArray::new(),
+        }
+    }
+
+    pub fn starting_position() -> BoardState {
+        let mut board = BoardState::empty();
+
+        board.add_pawn(TileCoord{x:3, y: 0}, PlayerSide::Top);
+        board.add_pawn(TileCoord{x:4, y: 0}, PlayerSide::Top);
+        board.add_pawn(TileCoord{x:5, y: 0}, PlayerSide::Top);
+        board.add_pawn(TileCoord{x:4, y: 1}, PlayerSide::Top);
+
+        board.add_pawn(TileCoord{x:3, y: 3}, PlayerSide::Bottom);
+        board.add_pawn(TileCoord{x:4, y: 4}, PlayerSide::Bottom);
+        board.add_pawn(TileCoord{x:5, y: 3}, PlayerSide::Bottom);
+
+        board
+    }
+
+    // Rows of tiles from the top of the board separated by '/', 't' and 'b' standing for the pawns of each side,
+    // followed by the side to move, the starting position being "...ttt.../....t..../........./...b.b.../..b.. b".
+    pub fn to_notation(&self) -> String {
+        let mut notation = String::new();
+        for index in 0..NUMBER_OF_TILES {
+            if index > 0 && index % TILES_ON_ROW == 0 {
+                notation.push('/');
+            }
+
+            notation.push(match self.tiles[index] {
+                Some(Pawn { player: PlayerSide::Top, .. }) => 't',
+                Some(Pawn { player: PlayerSide::Bottom, .. }) => 'b',
+                None => '.',
+            });
+        }
+
+        notation.push(' ');
+        notation.push(match self.current_player { PlayerSide::Top => 't', PlayerSide::Bottom => 'b' });
+        notation
+    }
+
+    pub fn from_notation(notation: &str) -> Result<BoardState, String> {
+        let mut parts = notation.split_whitespace();
+        let tiles = parts.next().ok_or("Empty notation")?;
+        let side = parts.next().ok_or("Missing side to move")?;
+
+        let mut board = BoardState::empty();
+        board.current_player = match side {
+            "t" => PlayerSide::Top,
+            "b" => PlayerSide::Bottom,
+            _ => return Err(format!("Invalid side to move {}", side)),
+        };
+
+        let mut index = 0;
+        for character in tiles.chars() {
+            if character == '/' {
+                continue;
+            }
+
+            if index >= NUMBER_OF_TILES {
+                return Err(String::from("Too many tiles"));
+            }
+
+            let player = match character {
+                't' => Some(PlayerSide::Top),
+                'b' => Some(PlayerSide::Bottom),
+                '.' => None,
+                _ => return Err(format!("Invalid tile {}", character)),
+            };
+
+            if let Some(player) = player {
+                let count = match player { PlayerSide::Top => board.top_pawns.count, PlayerSide::Bottom => board.bottom_pawns.count };
+                if count >= MAX_PAWN_NUMBER {
+                    return Err(format!("Too many {:?} pawns", player));
+                }
+
+                board.add_pawn(Grid::get_coord_from_index(index), player);
+            }
+
+            index += 1;
+        }
+
+        if index != NUMBER_OF_TILES {
+            return Err(String::from("Not enough tiles"));
+        }
+
+        Ok(board)
+    }
+
+    // Checks that the tiles and the pawn arrays of both sides describe the same pawns.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        for (player, pawn_array) in [(PlayerSide::Top, &self.top_pawns), (PlayerSide::Bottom, &self.bottom_pawns)] {
+            if pawn_array.count > MAX_PAWN_NUMBER {
+                return Err(format!("{0:?} has {1} pawns", player, pawn_array.count));
+            }
+
+            for table_index in 0..pawn_array.count {
+                let tile_index = pawn_array.tile_indexes[table_index];
+                match self.tiles.get(tile_index) {
+                    Some(Some(pawn)) if pawn.player == player && pawn.table_index == table_index => {},
+                    Some(Some(pawn)) => return Err(format!("{0:?} pawn {1} is on tile {2} holding {3:?}", player, table_index, tile_index, pawn)),
+                    Some(None) => return Err(format!("{0:?} pawn {1} is on the empty tile {2}", player, table_index, tile_index)),
+                    None => return Err(format!("{0:?} pawn {1} is on the invalid tile {2}", player, table_index, tile_index)),
+                }
+            }
+        }
+
+        for tile_index in 0..NUMBER_OF_TILES {
+            if let Some(pawn) = self.tiles[tile_index] {
+                let pawn_array = match pawn.player { PlayerSide::Top => &self.top_pawns, PlayerSide::Bottom => &self.bottom_pawns };
+                if pawn.table_index >= pawn_array.count || pawn_array.tile_indexes[pawn.table_index] != tile_index {
+                    return Err(format!("The pawn on tile {0} is missing from the {1:?} pawns", tile_index, pawn.player));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Two bits per tile and one for the side to move, equal for the same positions only.
+    pub fn get_key(&self) -> u128 {
+        let mut key = match self.current_player { PlayerSide::Top => 0, PlayerSide::Bottom => 1 };
+        for tile_index in 0..NUMBER_OF_TILES {
+            let pawn: u128 = match self.tiles[tile_index] {
+                Some(pawn) if pawn.player == PlayerSide::Top => 1,
+                Some(_) => 2,
+                None => continue,
+            };
+
+            key |= pawn << (1 + tile_index * 2);
+        }
+
+        key
+    }
+
+    pub fn get_winner(&self) -> Option<PlayerSide> {
+        if self.top_pawns.count == 0 {
+            return Some(PlayerSide::Bottom);
+        }
+        else if self.bottom_pawns.count == 0 {
+            return Some(PlayerSide::Top);
+        }
+
+        None
+    }
+
     pub fn add_pawn(&mut self, coord: TileCoord, player: PlayerSide) {
         let tile_index = Grid::get_index_from_coord(coord).unwrap();
         match self.tiles[tile_index] {
@@ -80,31 +229,7 @@ impl BoardState {
     }
     
     pub fn get_possible_moves(&self, tile_index: usize) -> Vec<usize> {
-        let coord = Grid::get_coord_from_index(tile_index);
-        let mut possible_plays = Vec::new();
-        
-        match Grid::get_tile_shape_from_index(tile_index) {
-            TileShape::Quad => {
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y - 1}) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y - 1}) {possible_plays.push(index)};
-            },
-
-            TileShape::Octo => {
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 2, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 2, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x    , y: coord.y + 1}) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x    , y: coord.y - 1}) {possible_plays.push(index)};
-
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y }) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y + 1}) {possible_plays.push(index)};
-                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y + 1}) {possible_plays.push(index)};
-            },
-        }
-
-        return possible_plays;
+        Grid::get_neighbours(tile_index)
     }
 
     pub fn get_possible_plays(&self, tile_index: usize, player_side: PlayerSide) -> Vec<usize> {
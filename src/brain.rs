@@ -1,36 +1,828 @@
 use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 
 use crate::game::*;
 use crate::grid::*;
+use crate::tiles::*;
 use crate::board::*;
+use crate::evaluation::*;
+use crate::opening_book::*;
+use crate::tablebase::*;
+
+pub const MATE_SCORE: i32 = 100000;
+pub const MAX_SEARCH_DEPTH: u32 = 64;
+pub const DEFAULT_RANDOM_MARGIN: i32 = 20;
+// Plies of the tablebase continuation reported as the expected line.
+pub const MAX_TABLEBASE_LINE: usize = 16;
 
 pub struct Brain {
 }
 
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub enum SearchAlgorithm {
+    Greedy,
+    AlphaBeta,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct AiConfig {
+    pub algorithm: SearchAlgorithm,
+    pub depth: u32,
+    pub profile: EvaluationProfile,
+    pub opening_book: Option<Arc<OpeningBook>>,
+    // Positions covered by the tablebase are played perfectly instead of being searched.
+    pub tablebase: Option<Arc<Tablebase>>,
+    // With a seed, the AI picks randomly among the plays scoring within random_margin of the best one.
+    // The choice only depends on the seed and the position so that a game can be replayed exactly.
+    pub seed: Option<u64>,
+    pub random_margin: i32,
+    pub threads: usize,
+    pub deterministic: bool,
+    pub quiescence: bool,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+pub struct SearchLimits {
+    pub depth: u32,
+    pub move_time: Option<Duration>,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Default)]
+#[derive(Debug)]
+pub struct SearchStats {
+    // Nodes whose plays were generated, and how many plays they had.
+    pub expanded_nodes: u64,
+    pub generated_plays: u64,
+    pub cutoffs: u64,
+    pub first_play_cutoffs: u64,
+    // Nodes searched past the depth to let the captures play out.
+    pub quiescence_nodes: u64,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SearchInfo {
+    pub depth: u32,
+    pub score: i32,
+    pub nodes: u64,
+    pub elapsed: Duration,
+    pub pv: Vec<(usize, usize)>,
+    pub stats: SearchStats,
+}
+
+pub struct SearchContext<'a> {
+    pub weights: &'a EvaluationWeights,
+    pub cancel: Option<&'a AtomicBool>,
+    pub deadline: Option<Instant>,
+    pub tablebase: Option<&'a Tablebase>,
+    pub nodes: u64,
+    pub stats: SearchStats,
+    // The root plays of the alpha-beta search are shared between that many threads.
+    pub threads: usize,
+    // Parallel threads then search every root play with a full window, giving the same play as a single thread
+    // instead of sharing the best score found so far to cut more branches.
+    pub deterministic: bool,
+    // The positions at the depth of the search are only evaluated once no capture is left worth playing.
+    pub quiescence: bool,
+    stopped: bool,
+}
+
+impl<'a> SearchContext<'a> {
+    pub fn new(weights: &'a EvaluationWeights, cancel: Option<&'a AtomicBool>) -> SearchContext<'a> {
+        SearchContext {
+            weights,
+            cancel,
+            deadline: None,
+            tablebase: None,
+            nodes: 0,
+            stats: SearchStats::default(),
+            threads: 1,
+            deterministic: false,
+            quiescence: false,
+            stopped: false,
+        }
+    }
+
+    // A context sharing the settings of this one, for a worker thread.
+    fn fork(&self) -> SearchContext<'a> {
+        SearchContext {
+            weights: self.weights,
+            cancel: self.cancel,
+            deadline: self.deadline,
+            tablebase: self.tablebase,
+            nodes: 0,
+            stats: SearchStats::default(),
+            threads: 1,
+            deterministic: self.deterministic,
+            quiescence: self.quiescence,
+            stopped: self.stopped,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self.stopped {
+            return true;
+        }
+
+        match self.cancel {
+            Some(cancel) => cancel.load(Ordering::Relaxed),
+            None => false,
+        }
+    }
+
+    // Same as is_cancelled, also stopping once the deadline is reached. The clock is only read every few nodes.
+    // u64::is_multiple_of needs Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    fn should_stop(&mut self) -> bool {
+        if self.is_cancelled() {
+            self.stopped = true;
+        }
+        else if let Some(deadline) = self.deadline {
+            self.stopped = self.nodes % 1024 == 0 && Instant::now() >= deadline;
+        }
+
+        self.stopped
+    }
+}
+
+impl SearchStats {
+    pub fn branching_factor(&self) -> f64 {
+        self.generated_plays as f64 / self.expanded_nodes.max(1) as f64
+    }
+
+    // Share of the cutoffs given by the first play searched, showing how good the play ordering is.
+    pub fn first_play_cutoff_rate(&self) -> f64 {
+        self.first_play_cutoffs as f64 / self.cutoffs.max(1) as f64
+    }
+
+    pub fn add(&mut self, other: &SearchStats) {
+        self.expanded_nodes += other.expanded_nodes;
+        self.generated_plays += other.generated_plays;
+        self.cutoffs += other.cutoffs;
+        self.first_play_cutoffs += other.first_play_cutoffs;
+        self.quiescence_nodes += other.quiescence_nodes;
+    }
+}
+
+impl SearchInfo {
+    pub fn nodes_per_second(&self) -> u64 {
+        (self.nodes as f64 / self.elapsed.as_secs_f64().max(1e-6)) as u64
+    }
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth,
+            move_time: None,
+        }
+    }
+}
+
+impl SearchAlgorithm {
+    pub fn from_name(name: &str) -> Option<SearchAlgorithm> {
+        match name {
+            "greedy" => Some(SearchAlgorithm::Greedy),
+            "alphabeta" => Some(SearchAlgorithm::AlphaBeta),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SearchAlgorithm::Greedy => "greedy",
+            SearchAlgorithm::AlphaBeta => "alphabeta",
+        }
+    }
+
+    pub fn default_depth(self) -> u32 {
+        match self {
+            SearchAlgorithm::Greedy => 10,
+            SearchAlgorithm::AlphaBeta => 4,
+        }
+    }
+}
+
+impl AiConfig {
+    pub fn new(algorithm: SearchAlgorithm, profile: EvaluationProfile) -> AiConfig {
+        AiConfig {
+            algorithm,
+            depth: algorithm.default_depth(),
+            profile,
+            opening_book: None,
+            tablebase: None,
+            seed: None,
+            random_margin: DEFAULT_RANDOM_MARGIN,
+            threads: 1,
+            deterministic: true,
+            quiescence: true,
+        }
+    }
+
+    fn get_position_rng(&self, board: &BoardState) -> Option<StdRng> {
+        // FNV-1a, the standard hashers are not guaranteed to give the same result from one build to another.
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in board.to_notation().bytes() {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+
+        self.seed.map(|seed| StdRng::seed_from_u64(seed ^ hash))
+    }
+
+    fn create_context<'a>(&'a self, cancel: Option<&'a AtomicBool>) -> SearchContext<'a> {
+        let mut context = SearchContext::new(&self.profile.weights, cancel);
+        context.tablebase = self.tablebase.as_deref();
+        context.threads = self.threads;
+        context.deterministic = self.deterministic;
+        context.quiescence = self.quiescence;
+        context
+    }
+
+    pub fn search_best_play(&self, board: &BoardState) -> Option<(usize, usize)> {
+        self.search(board, None)
+    }
+
+    // The count best plays with their scores and expected continuations, best first, for studying a position.
+    // The opening book and the seed are left out, on_lines being called each time the lines make progress.
+    pub fn search_lines<F: FnMut(&[SearchInfo])>(&self, board: &BoardState, count: usize, cancel: Option<&AtomicBool>, mut on_lines: F) -> Vec<SearchInfo> {
+        let start = Instant::now();
+        let mut context = self.create_context(cancel);
+        match self.algorithm {
+            SearchAlgorithm::Greedy => {
+                let lines: Vec<SearchInfo> = Brain::greedy_lines(board, self.depth, &mut context).into_iter()
+                    .take(count)
+                    .map(|(score, pv)| SearchInfo {
+                        depth: self.depth,
+                        score,
+                        nodes: context.nodes,
+                        elapsed: start.elapsed(),
+                        pv,
+                        stats: context.stats,
+                    })
+                    .collect();
+
+                if context.is_cancelled() {
+                    return Vec::new();
+                }
+
+                on_lines(&lines);
+                lines
+            },
+            SearchAlgorithm::AlphaBeta => Brain::search_multi_pv(board, &SearchLimits::depth(self.depth), count, &mut context, on_lines),
+        }
+    }
+
+    // The search gives up and returns None as soon as the cancel flag is raised.
+    pub fn search(&self, board: &BoardState, cancel: Option<&AtomicBool>) -> Option<(usize, usize)> {
+        self.search_with_info(board, cancel, |_| {})
+    }
+
+    // Same as search, on_info being called with the expected continuation each time the search makes progress.
+    pub fn search_with_info<F: FnMut(&SearchInfo)>(&self, board: &BoardState, cancel: Option<&AtomicBool>, mut on_info: F) -> Option<(usize, usize)> {
+        let start = Instant::now();
+        let mut rng = self.get_position_rng(board);
+        if let Some(book) = &self.opening_book {
+            let play = match &mut rng {
+                Some(rng) => book.pick_move(board, rng),
+                None => book.pick_move(board, &mut rand::thread_rng()),
+            };
+
+            if play.is_some() {
+                return play;
+            }
+        }
+
+        if let Some(tablebase) = &self.tablebase {
+            if let Some((play, result)) = tablebase.get_best_play(board) {
+                let pv = tablebase.get_line(board, MAX_TABLEBASE_LINE);
+                on_info(&SearchInfo {
+                    depth: pv.len() as u32,
+                    score: result.get_score(0),
+                    nodes: 0,
+                    elapsed: start.elapsed(),
+                    pv,
+                    stats: SearchStats::default(),
+                });
+
+                return Some(play);
+            }
+        }
+
+        let mut context = self.create_context(cancel);
+        // The iterative deepening reports its progress by itself.
+        let is_iterative = self.algorithm == SearchAlgorithm::AlphaBeta && rng.is_none();
+        let (score, pv) = match (self.algorithm, &mut rng) {
+            (SearchAlgorithm::Greedy, rng) => {
+                let lines = Brain::greedy_lines_with_info(board, self.depth, &mut context, &mut on_info);
+                let scores: Vec<((usize, usize), i32)> = lines.iter().map(|(score, line)| (line[0], *score)).collect();
+                let play = match rng {
+                    Some(rng) => Brain::pick_play(&scores, self.random_margin, rng),
+                    None => scores.first().map(|score| score.0),
+                };
+
+                match lines.into_iter().find(|(_, line)| Some(line[0]) == play) {
+                    Some((score, line)) => (score, line),
+                    None => (0, Vec::new()),
+                }
+            },
+            (SearchAlgorithm::AlphaBeta, None) => {
+                let limits = SearchLimits::depth(self.depth);
+                match Brain::iterative_deepening(board, &limits, &mut context, &mut on_info) {
+                    Some(info) => (info.score, info.pv),
+                    None => (0, Vec::new()),
+                }
+            },
+            (SearchAlgorithm::AlphaBeta, Some(rng)) => {
+                let scores = Brain::score_plays(board, self.depth, &mut context);
+                match Brain::pick_play(&scores, self.random_margin, rng) {
+                    Some(play) => (scores.iter().find(|score| score.0 == play).unwrap().1, vec![play]),
+                    None => (0, Vec::new()),
+                }
+            },
+        };
+
+        let play = pv.first().copied();
+        if !is_iterative && !pv.is_empty() && !context.is_cancelled() {
+            on_info(&SearchInfo {
+                depth: self.depth,
+                score,
+                nodes: context.nodes,
+                elapsed: start.elapsed(),
+                pv,
+                stats: context.stats,
+            });
+        }
+
+        if context.is_cancelled() {
+            return None;
+        }
+
+        play
+    }
+}
+
+impl std::fmt::Display for AiConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{0} depth {1} ({2})", self.algorithm.name(), self.depth, self.profile.name)?;
+        if self.opening_book.is_some() {
+            write!(f, " with opening book")?;
+        }
+
+        if self.tablebase.is_some() {
+            write!(f, " with tablebase")?;
+        }
+
+        if self.threads > 1 {
+            write!(f, " on {} threads", self.threads)?;
+        }
+
+        if self.algorithm == SearchAlgorithm::AlphaBeta && !self.quiescence {
+            write!(f, " without quiescence")?;
+        }
+
+        if let Some(seed) = self.seed {
+            write!(f, " seed {0} margin {1}", seed, self.random_margin)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Brain {
 
-    pub fn search_best_play(board: &BoardState, iteration: u32) -> Option<(usize, usize)>{
+    pub fn search_best_play(board: &BoardState, iteration: u32, context: &mut SearchContext) -> Option<(usize, usize)>{
+        let scores = Brain::score_greedy_plays(board, iteration, context);
+        if context.is_cancelled() {
+            return None;
+        }
+
+        /*
+        println!("{:?}", scores);
+        println!("Of {0} plays, picked {1}->{2}", scores.len(), Grid::get_coord_from_index(scores[0].0.0), Grid::get_coord_from_index(scores[0].0.1));
+         // */
+        return scores.first().map(|score| score.0);
+    }
+
+    // Scores of the greedy search, best first.
+    pub fn score_greedy_plays(board: &BoardState, iteration: u32, context: &mut SearchContext) -> Vec<((usize, usize), i32)> {
+        Brain::greedy_lines(board, iteration, context).into_iter().map(|(score, line)| (line[0], score)).collect()
+    }
+
+    // Every play followed by the line the greedy search expects, with its score, best first.
+    pub fn greedy_lines(board: &BoardState, iteration: u32, context: &mut SearchContext) -> Vec<(i32, Vec<(usize, usize)>)> {
+        Brain::greedy_lines_with_info(board, iteration, context, |_| {})
+    }
+
+    // Same as greedy_lines, on_info being called after each play with the best line found so far.
+    pub fn greedy_lines_with_info<F: FnMut(&SearchInfo)>(board: &BoardState, iteration: u32, context: &mut SearchContext, mut on_info: F) -> Vec<(i32, Vec<(usize, usize)>)> {
+        let start = Instant::now();
         let plays = Brain::find_all_plays(board, board.current_player);
-        let mut scores = Vec::new();
+        let mut lines: Vec<(i32, Vec<(usize, usize)>)> = Vec::new();
+        let mut best: Option<usize> = None;
         for play in plays {
+            if context.is_cancelled() {
+                break;
+            }
+
             let next_board = board.make_move(play.0, play.1);
-            let predicted_result = Brain::explore_branch(next_board, iteration * 2 - 1);
+            let mut line = vec![play];
+            let predicted_result = Brain::explore_branch(next_board, iteration * 2 - 1, context, &mut line);
             
             let predicted_result = if predicted_result.1 != board.current_player {-predicted_result.0} else {predicted_result.0};
 
-            scores.push((play, predicted_result));
+            lines.push((predicted_result, line));
+            let best_index = match best {
+                Some(index) if lines[index].0 >= predicted_result => index,
+                _ => lines.len() - 1,
+            };
+
+            best = Some(best_index);
+            on_info(&SearchInfo {
+                depth: iteration,
+                score: lines[best_index].0,
+                nodes: context.nodes,
+                elapsed: start.elapsed(),
+                pv: lines[best_index].1.clone(),
+                stats: context.stats,
+            });
         }
 
-        scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().reverse());
+        lines.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap().reverse());
+        lines
+    }
 
-        /*
-        println!("{:?}", scores);
-        println!("Of {0} plays, picked {1}->{2}", scores.len(), Grid::get_coord_from_index(scores[0].0.0), Grid::get_coord_from_index(scores[0].0.1));
-         // */
-        return Some(scores[0].0);
+    // Picks uniformly among the plays scoring within margin of the best one.
+    pub fn pick_play<R: Rng>(scores: &[((usize, usize), i32)], margin: i32, rng: &mut R) -> Option<(usize, usize)> {
+        let best_score = scores.iter().map(|score| score.1).max()?;
+        let candidates: Vec<(usize, usize)> = scores.iter()
+            .filter(|score| score.1 >= best_score - margin)
+            .map(|score| score.0)
+            .collect();
+
+        Some(candidates[rng.gen_range(0..candidates.len())])
+    }
+
+    pub fn search_alpha_beta(board: &BoardState, depth: u32, context: &mut SearchContext) -> Option<(usize, usize)> {
+        let mut pv = Vec::new();
+        Brain::search_root(board, depth, None, context, &mut pv);
+        if context.is_cancelled() {
+            return None;
+        }
+
+        pv.first().copied()
+    }
+
+    // Searches one depth after the other until the limits are reached, calling on_info after every completed depth.
+    // An interrupted depth is discarded, the result being the one of the last completed depth.
+    pub fn iterative_deepening<F: FnMut(&SearchInfo)>(board: &BoardState, limits: &SearchLimits, context: &mut SearchContext, mut on_info: F) -> Option<SearchInfo> {
+        let start = Instant::now();
+        if let Some(move_time) = limits.move_time {
+            context.deadline = Some(start + move_time);
+        }
+
+        let mut result: Option<SearchInfo> = None;
+        for depth in 1..=limits.depth.min(MAX_SEARCH_DEPTH) {
+            let mut pv = Vec::new();
+            let pv_play = result.as_ref().and_then(|info| info.pv.first().copied());
+            let score = Brain::search_root(board, depth, pv_play, context, &mut pv);
+            if context.is_cancelled() || pv.is_empty() {
+                break;
+            }
+
+            let info = SearchInfo {
+                depth,
+                score,
+                nodes: context.nodes,
+                elapsed: start.elapsed(),
+                pv,
+                stats: context.stats,
+            };
+
+            on_info(&info);
+            result = Some(info);
+        }
+
+        if result.is_none() {
+            // Not even the first depth could be completed, any legal play is better than none.
+            let plays = Brain::order_plays(board, Brain::find_all_plays(board, board.current_player));
+            result = plays.first().map(|play| SearchInfo {
+                depth: 0,
+                score: 0,
+                nodes: context.nodes,
+                elapsed: start.elapsed(),
+                pv: vec![*play],
+                stats: context.stats,
+            });
+        }
+
+        result
     }
 
-    fn explore_branch(board: BoardState, layer: u32) -> (i32, PlayerSide){
+    // Same as iterative_deepening for the count best plays, each depth starting with the plays of the previous lines.
+    // The root plays are searched on a single thread.
+    pub fn search_multi_pv<F: FnMut(&[SearchInfo])>(board: &BoardState, limits: &SearchLimits, count: usize, context: &mut SearchContext, mut on_lines: F) -> Vec<SearchInfo> {
+        let start = Instant::now();
+        if let Some(move_time) = limits.move_time {
+            context.deadline = Some(start + move_time);
+        }
+
+        let mut result: Vec<SearchInfo> = Vec::new();
+        for depth in 1..=limits.depth.min(MAX_SEARCH_DEPTH) {
+            let previous_plays: Vec<(usize, usize)> = result.iter().map(|info| info.pv[0]).collect();
+            let lines = Brain::search_root_multi_pv(board, depth, count.max(1), &previous_plays, context);
+            if context.is_cancelled() || lines.is_empty() {
+                break;
+            }
+
+            result = lines.into_iter()
+                .map(|(score, pv)| SearchInfo {
+                    depth,
+                    score,
+                    nodes: context.nodes,
+                    elapsed: start.elapsed(),
+                    pv,
+                    stats: context.stats,
+                })
+                .collect();
+
+            on_lines(&result);
+        }
+
+        result
+    }
+
+    fn search_root(board: &BoardState, depth: u32, pv_play: Option<(usize, usize)>, context: &mut SearchContext, pv: &mut Vec<(usize, usize)>) -> i32 {
+        let mut plays = Brain::order_plays(board, Brain::find_all_plays(board, board.current_player));
+        if let Some(position) = plays.iter().position(|play| Some(*play) == pv_play) {
+            let play = plays.remove(position);
+            plays.insert(0, play);
+        }
+
+        if context.threads > 1 && plays.len() > 1 {
+            return Brain::search_root_parallel(board, depth, &plays, context, pv);
+        }
+
+        let mut alpha = -MATE_SCORE;
+        for play in plays {
+            let next_board = board.make_move(play.0, play.1);
+            let mut child_pv = Vec::new();
+            let score = -Brain::negamax(&next_board, depth.max(1) - 1, 1, -MATE_SCORE, -alpha, context, &mut child_pv);
+            if context.is_cancelled() {
+                break;
+            }
+
+            if pv.is_empty() || score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(play);
+                pv.extend(child_pv);
+            }
+        }
+
+        alpha
+    }
+
+    // Root plays are handed out one at a time to the threads. The best play is the first one in order among the best
+    // scores, as in the single threaded search. A play searched against the best score shared by the other threads
+    // and not beating it only gives a bound, it is left out of the choice.
+    fn search_root_parallel(board: &BoardState, depth: u32, plays: &[(usize, usize)], context: &mut SearchContext, pv: &mut Vec<(usize, usize)>) -> i32 {
+        let next_play = AtomicUsize::new(0);
+        let shared_alpha = AtomicI32::new(-MATE_SCORE);
+        let thread_count = context.threads.min(plays.len());
+        let mut results = Vec::new();
+        let mut is_cancelled = false;
+
+        thread::scope(|scope| {
+            let mut workers = Vec::new();
+            for _ in 0..thread_count {
+                let mut worker_context = context.fork();
+                let next_play = &next_play;
+                let shared_alpha = &shared_alpha;
+                workers.push(scope.spawn(move || {
+                    let mut scores = Vec::new();
+                    loop {
+                        let index = next_play.fetch_add(1, Ordering::Relaxed);
+                        if index >= plays.len() {
+                            break;
+                        }
+
+                        let alpha = match worker_context.deterministic {
+                            true => -MATE_SCORE,
+                            false => shared_alpha.load(Ordering::Relaxed),
+                        };
+
+                        let play = plays[index];
+                        let next_board = board.make_move(play.0, play.1);
+                        let mut child_pv = Vec::new();
+                        let score = -Brain::negamax(&next_board, depth.max(1) - 1, 1, -MATE_SCORE, -alpha, &mut worker_context, &mut child_pv);
+                        if worker_context.is_cancelled() {
+                            break;
+                        }
+
+                        if alpha > -MATE_SCORE && score <= alpha {
+                            continue;
+                        }
+
+                        shared_alpha.fetch_max(score, Ordering::Relaxed);
+                        let mut play_pv = vec![play];
+                        play_pv.extend(child_pv);
+                        scores.push((index, score, play_pv));
+                    }
+
+                    (scores, worker_context.nodes, worker_context.stats, worker_context.is_cancelled())
+                }));
+            }
+
+            for worker in workers {
+                let (scores, nodes, stats, cancelled) = worker.join().unwrap();
+                results.extend(scores);
+                context.nodes += nodes;
+                context.stats.add(&stats);
+                is_cancelled |= cancelled;
+            }
+        });
+
+        if is_cancelled {
+            context.stopped = true;
+        }
+
+        results.sort_by(|left, right| left.1.cmp(&right.1).reverse().then(left.0.cmp(&right.0)));
+        match results.into_iter().next() {
+            Some((_, score, best_pv)) => {
+                *pv = best_pv;
+                score
+            },
+            None => -MATE_SCORE,
+        }
+    }
+
+    // A play is only searched for its exact score when it could enter the lines, the window starting at the score of
+    // the last one once there are count of them. Plays with the same score keep the order in which they were searched.
+    fn search_root_multi_pv(board: &BoardState, depth: u32, count: usize, previous_plays: &[(usize, usize)], context: &mut SearchContext) -> Vec<(i32, Vec<(usize, usize)>)> {
+        let mut plays = Brain::order_plays(board, Brain::find_all_plays(board, board.current_player));
+        for previous_play in previous_plays.iter().rev() {
+            if let Some(position) = plays.iter().position(|play| play == previous_play) {
+                let play = plays.remove(position);
+                plays.insert(0, play);
+            }
+        }
+
+        let mut lines: Vec<(i32, Vec<(usize, usize)>)> = Vec::new();
+        for play in plays {
+            let alpha = match lines.len() >= count {
+                true => lines[count - 1].0,
+                false => -MATE_SCORE,
+            };
+
+            let next_board = board.make_move(play.0, play.1);
+            let mut child_pv = Vec::new();
+            let score = -Brain::negamax(&next_board, depth.max(1) - 1, 1, -MATE_SCORE, -alpha, context, &mut child_pv);
+            if context.is_cancelled() {
+                break;
+            }
+
+            if lines.len() < count || score > alpha {
+                let mut pv = vec![play];
+                pv.extend(child_pv);
+                let position = lines.iter().position(|line| line.0 < score).unwrap_or(lines.len());
+                lines.insert(position, (score, pv));
+                lines.truncate(count);
+            }
+        }
+
+        lines
+    }
+
+    // Scores every play with a full window so that the scores can be compared with one another.
+    pub fn score_plays(board: &BoardState, depth: u32, context: &mut SearchContext) -> Vec<((usize, usize), i32)> {
+        let plays = Brain::order_plays(board, Brain::find_all_plays(board, board.current_player));
+        let mut scores = Vec::new();
+        for play in plays {
+            let next_board = board.make_move(play.0, play.1);
+            let score = -Brain::negamax(&next_board, depth.max(1) - 1, 1, -MATE_SCORE, MATE_SCORE, context, &mut Vec::new());
+            scores.push((play, score));
+        }
+
+        scores
+    }
+
+    // Ends of the game score on the mate scale of the solver : the side to move being eliminated ply plies after the root
+    // scores -(MATE_SCORE - ply), above any evaluation, the tablebase results being counted in plies from the root too.
+    fn negamax(board: &BoardState, depth: u32, ply: u32, mut alpha: i32, beta: i32, context: &mut SearchContext, pv: &mut Vec<(usize, usize)>) -> i32 {
+        context.nodes += 1;
+        if context.should_stop() {
+            return 0;
+        }
+
+        if let Some(result) = context.tablebase.and_then(|tablebase| tablebase.probe(board)) {
+            return result.get_score(ply);
+        }
+
+        if board.get_winner().is_some() {
+            return -(MATE_SCORE - ply as i32);
+        }
+
+        if depth == 0 {
+            return match context.quiescence {
+                true => Brain::quiescence(board, ply, alpha, beta, context, pv),
+                false => Brain::evaluate_play(*board, context.weights),
+            };
+        }
+
+        let plays = Brain::order_plays(board, Brain::find_all_plays(board, board.current_player));
+        if plays.is_empty() {
+            return -(MATE_SCORE - ply as i32);
+        }
+
+        context.stats.expanded_nodes += 1;
+        context.stats.generated_plays += plays.len() as u64;
+        for (index, play) in plays.into_iter().enumerate() {
+            let next_board = board.make_move(play.0, play.1);
+            let mut child_pv = Vec::new();
+            let score = -Brain::negamax(&next_board, depth - 1, ply + 1, -beta, -alpha, context, &mut child_pv);
+            if score >= beta {
+                context.stats.cutoffs += 1;
+                if index == 0 {
+                    context.stats.first_play_cutoffs += 1;
+                }
+
+                return score;
+            }
+
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(play);
+                pv.extend(child_pv);
+            }
+        }
+
+        alpha
+    }
+
+    // Searches the captures only, the side to move being free to stop capturing and keep the evaluation of the position.
+    // Every capture removing a pawn, the search always ends.
+    fn quiescence(board: &BoardState, ply: u32, mut alpha: i32, beta: i32, context: &mut SearchContext, pv: &mut Vec<(usize, usize)>) -> i32 {
+        context.nodes += 1;
+        context.stats.quiescence_nodes += 1;
+        if context.should_stop() {
+            return 0;
+        }
+
+        if let Some(result) = context.tablebase.and_then(|tablebase| tablebase.probe(board)) {
+            return result.get_score(ply);
+        }
+
+        if board.get_winner().is_some() {
+            return -(MATE_SCORE - ply as i32);
+        }
+
+        let evaluation = Brain::evaluate_play(*board, context.weights);
+        if evaluation >= beta {
+            return evaluation;
+        }
+
+        alpha = alpha.max(evaluation);
+        let captures: Vec<(usize, usize)> = Brain::find_all_plays(board, board.current_player).into_iter()
+            .filter(|play| board.tiles[play.1].is_some())
+            .collect();
+        for capture in captures {
+            let next_board = board.make_move(capture.0, capture.1);
+            let mut child_pv = Vec::new();
+            let score = -Brain::quiescence(&next_board, ply + 1, -beta, -alpha, context, &mut child_pv);
+            if score >= beta {
+                return score;
+            }
+
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(capture);
+                pv.extend(child_pv);
+            }
+        }
+
+        alpha
+    }
+
+    // Captures are searched first as they are the most likely to cause cutoffs.
+    pub fn order_plays(board: &BoardState, mut plays: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        plays.sort_by_key(|play| board.tiles[play.1].is_none());
+        plays
+    }
+
+    fn explore_branch(board: BoardState, layer: u32, context: &mut SearchContext, line: &mut Vec<(usize, usize)>) -> (i32, PlayerSide){
         let mut result = -10000;
         let mut current_board = board;
         for _ in 0..layer {
@@ -43,19 +835,45 @@ impl Brain {
             let mut ranked_plays = Vec::new();
             for play in plays {
                 let next_board = current_board.make_move(play.0, play.1);
-                let evaluation = Brain::evaluate_play(next_board);
-                ranked_plays.push((next_board, evaluation));
+                let evaluation = Brain::evaluate_play(next_board, context.weights);
+                ranked_plays.push((play, next_board, evaluation));
             }
 
-            ranked_plays.sort_by(|left, right| left.1.cmp(&right.1).reverse());
-            result = ranked_plays[0].1;
-            current_board = ranked_plays[0].0;
+            context.nodes += ranked_plays.len() as u64;
+            ranked_plays.sort_by(|left, right| left.2.cmp(&right.2).reverse());
+            result = ranked_plays[0].2;
+            current_board = ranked_plays[0].1;
+            line.push(ranked_plays[0].0);
         }
 
         return (result, current_board.current_player);
     }
 
-    fn find_all_plays(board: &BoardState, player_side: PlayerSide) -> Vec<(usize, usize)> {
+    // Number of positions reached after exactly depth plies, the games ending before not being counted.
+    pub fn perft(board: &BoardState, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        if board.get_winner().is_some() {
+            return 0;
+        }
+
+        let plays = Brain::find_all_plays(board, board.current_player);
+        match depth {
+            1 => plays.len() as u64,
+            _ => plays.iter().map(|play| Brain::perft(&board.make_move(play.0, play.1), depth - 1)).sum(),
+        }
+    }
+
+    // The perft count of each play, to find the play whose count differs from a reference one.
+    pub fn perft_divide(board: &BoardState, depth: u32) -> Vec<((usize, usize), u64)> {
+        Brain::find_all_plays(board, board.current_player).into_iter()
+            .map(|play| (play, Brain::perft(&board.make_move(play.0, play.1), depth.max(1) - 1)))
+            .collect()
+    }
+
+    pub fn find_all_plays(board: &BoardState, player_side: PlayerSide) -> Vec<(usize, usize)> {
         let mut all_plays = Vec::new();
 
         let pawn_indexes = match player_side { PlayerSide::Top => board.top_pawns, PlayerSide::Bottom => board.bottom_pawns };
@@ -96,25 +914,31 @@ impl Brain {
         return (first_layer, second_layer);
     }
 
-    pub fn evaluate_play(board: BoardState) -> i32 {
+    pub fn evaluate_play(board: BoardState, weights: &EvaluationWeights) -> i32 {
+        Brain::evaluate_breakdown(&board, weights).total()
+    }
+
+    // The terms weighted at 0 are not computed.
+    pub fn evaluate_breakdown(board: &BoardState, weights: &EvaluationWeights) -> EvaluationBreakdown {
         let my_pawns = match board.current_player { PlayerSide::Top => board.top_pawns, PlayerSide::Bottom => board.bottom_pawns };
         let their_pawns = match board.current_player { PlayerSide::Top => board.bottom_pawns, PlayerSide::Bottom => board.top_pawns };
 
-        let mut score = 0;
-        score = score + my_pawns.count as i32 * 190;
-        score = score - their_pawns.count as i32 * 200;
+        let mut breakdown = EvaluationBreakdown {
+            material: my_pawns.count as i32 * weights.my_pawn - their_pawns.count as i32 * weights.their_pawn,
+            ..Default::default()
+        };
 
         for index in 0..my_pawns.count {
-            let two_layers = Brain::get_two_layer_moves(board, my_pawns.tile_indexes[index]);
+            let two_layers = Brain::get_two_layer_moves(*board, my_pawns.tile_indexes[index]);
             for tile_index in two_layers.0 {
                 match board.tiles[tile_index] {
                     None => {},
                     Some(pawn) => {
                         if pawn.player == board.current_player {
-                            score = score + 10;
+                            breakdown.supported_friend += weights.supported_friend;
                         }
                         else {
-                            score = score - 100;
+                            breakdown.adjacent_enemy -= weights.adjacent_enemy;
                         }
                     }
                 }
@@ -125,7 +949,7 @@ impl Brain {
                     None => {},
                     Some(pawn) => {
                         if pawn.player != board.current_player {
-                            score = score + 30;
+                            breakdown.enemy_at_two += weights.enemy_at_two;
                         }
                     }
                 }
@@ -133,13 +957,56 @@ impl Brain {
         }
 
         if my_pawns.count == 0 {
-            score = score - 2000;
+            breakdown.elimination -= weights.elimination;
         }
 
         if their_pawns.count == 0 {
-            score = score + 2000;
+            breakdown.elimination += weights.elimination;
         }
 
-        return score;
+        let my_side = board.current_player;
+        let their_side = board.current_player.reverse();
+        if weights.mobility != 0 {
+            let my_plays = Brain::find_all_plays(board, my_side).len() as i32;
+            let their_plays = Brain::find_all_plays(board, their_side).len() as i32;
+            breakdown.mobility = (my_plays - their_plays) * weights.mobility;
+        }
+
+        for (pawns, player, sign) in [(&my_pawns, my_side, 1), (&their_pawns, their_side, -1)] {
+            for tile_index in &pawns.tile_indexes[..pawns.count] {
+                if weights.octo_tile != 0 && matches!(Grid::get_tile_shape_from_index(*tile_index), TileShape::Octo) {
+                    breakdown.octo_tile += sign * weights.octo_tile;
+                }
+
+                if weights.advancement != 0 {
+                    breakdown.advancement += sign * Brain::get_advancement(*tile_index, player) * weights.advancement;
+                }
+
+                if weights.cohesion != 0 {
+                    let two_layers = Brain::get_two_layer_moves(*board, *tile_index);
+                    let has_friend = two_layers.0.iter().chain(two_layers.1.iter())
+                        .any(|tile_index| matches!(board.tiles[*tile_index], Some(pawn) if pawn.player == player));
+                    if has_friend {
+                        breakdown.cohesion += sign * weights.cohesion;
+                    }
+                }
+            }
+        }
+
+        breakdown
+    }
+
+    // Half rows between the tile and the first row of the player, quad and octo rows alternating.
+    fn get_advancement(tile_index: usize, player: PlayerSide) -> i32 {
+        let tile_index = match player {
+            PlayerSide::Top => tile_index,
+            PlayerSide::Bottom => Grid::get_flipped_index(tile_index),
+        };
+
+        let coord = Grid::get_coord_from_index(tile_index);
+        match Grid::get_tile_shape(coord) {
+            TileShape::Quad => coord.y * 2,
+            TileShape::Octo => coord.y * 2 + 1,
+        }
     }
 }
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::{env, path};
+
+pub struct CommandLine {
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl CommandLine {
+    pub fn from_env() -> CommandLine {
+        CommandLine::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> CommandLine {
+        let mut options = HashMap::new();
+        let mut flags = Vec::new();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name.to_owned(),
+                None => {
+                    eprintln!("Ignoring unexpected argument {}", arg);
+                    continue;
+                }
+            };
+
+            match args.peek() {
+                Some(value) if !value.starts_with("--") => {
+                    options.insert(name, args.next().unwrap());
+                },
+                _ => flags.push(name),
+            }
+        }
+
+        CommandLine { options, flags }
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|value| value.as_str())
+    }
+
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        match self.options.get(name) {
+            Some(value) => match value.parse() {
+                Ok(value) => value,
+                Err(_) => panic!("Invalid value for --{0} : {1}", name, value),
+            },
+            None => default,
+        }
+    }
+}
+
+pub fn get_resource_dir() -> path::PathBuf {
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        let mut path = path::PathBuf::from(manifest_dir);
+        path.push("resources");
+        path
+    } else {
+        path::PathBuf::from("./resources")
+    }
+}
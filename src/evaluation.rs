@@ -0,0 +1,178 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::*;
+
+pub const EVALUATION_PROFILES_PATH: &str = "/evaluation_profiles.json";
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct EvaluationWeights {
+    pub my_pawn: i32,
+    pub their_pawn: i32,
+    pub supported_friend: i32,
+    pub adjacent_enemy: i32,
+    pub enemy_at_two: i32,
+    pub elimination: i32,
+    // The terms below are switched off at 0, profiles written before them leaving them out.
+    // Difference between the number of plays of both sides.
+    #[serde(default)]
+    pub mobility: i32,
+    // Per pawn on an octo tile, which reaches more tiles than a quad one.
+    #[serde(default)]
+    pub octo_tile: i32,
+    // Per half row a pawn went forward from the side it started on.
+    #[serde(default)]
+    pub advancement: i32,
+    // Per pawn with a friendly pawn at most two moves away.
+    #[serde(default)]
+    pub cohesion: i32,
+}
+
+// The share of each term in the evaluation of a position, from the side to move's point of view.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct EvaluationBreakdown {
+    pub material: i32,
+    pub supported_friend: i32,
+    pub adjacent_enemy: i32,
+    pub enemy_at_two: i32,
+    pub elimination: i32,
+    pub mobility: i32,
+    pub octo_tile: i32,
+    pub advancement: i32,
+    pub cohesion: i32,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct EvaluationProfile {
+    pub name: String,
+    pub weights: EvaluationWeights,
+}
+
+impl Default for EvaluationWeights {
+    fn default() -> Self {
+        EvaluationWeights {
+            my_pawn: 190,
+            their_pawn: 200,
+            supported_friend: 10,
+            adjacent_enemy: 100,
+            enemy_at_two: 30,
+            elimination: 2000,
+            mobility: 0,
+            octo_tile: 0,
+            advancement: 0,
+            cohesion: 0,
+        }
+    }
+}
+
+impl EvaluationWeights {
+    pub const TUNABLE_NAMES: [&'static str; 9] = ["my_pawn", "their_pawn", "supported_friend", "adjacent_enemy", "enemy_at_two",
+        "mobility", "octo_tile", "advancement", "cohesion"];
+
+    pub fn get_tunable(&self) -> [i32; 9] {
+        [self.my_pawn, self.their_pawn, self.supported_friend, self.adjacent_enemy, self.enemy_at_two,
+            self.mobility, self.octo_tile, self.advancement, self.cohesion]
+    }
+
+    pub fn with_tunable(&self, values: [i32; 9]) -> EvaluationWeights {
+        EvaluationWeights {
+            my_pawn: values[0],
+            their_pawn: values[1],
+            supported_friend: values[2],
+            adjacent_enemy: values[3],
+            enemy_at_two: values[4],
+            mobility: values[5],
+            octo_tile: values[6],
+            advancement: values[7],
+            cohesion: values[8],
+            ..*self
+        }
+    }
+
+    // Sets a weight by the name of its field.
+    pub fn set_weight(&mut self, name: &str, value: i32) -> Result<(), String> {
+        let weight = match name {
+            "my_pawn" => &mut self.my_pawn,
+            "their_pawn" => &mut self.their_pawn,
+            "supported_friend" => &mut self.supported_friend,
+            "adjacent_enemy" => &mut self.adjacent_enemy,
+            "enemy_at_two" => &mut self.enemy_at_two,
+            "elimination" => &mut self.elimination,
+            "mobility" => &mut self.mobility,
+            "octo_tile" => &mut self.octo_tile,
+            "advancement" => &mut self.advancement,
+            "cohesion" => &mut self.cohesion,
+            _ => return Err(format!("unknown evaluation weight {}", name)),
+        };
+
+        *weight = value;
+        Ok(())
+    }
+}
+
+impl EvaluationBreakdown {
+    pub fn get_terms(&self) -> [(&'static str, i32); 9] {
+        [
+            ("material", self.material),
+            ("supported_friend", self.supported_friend),
+            ("adjacent_enemy", self.adjacent_enemy),
+            ("enemy_at_two", self.enemy_at_two),
+            ("elimination", self.elimination),
+            ("mobility", self.mobility),
+            ("octo_tile", self.octo_tile),
+            ("advancement", self.advancement),
+            ("cohesion", self.cohesion),
+        ]
+    }
+
+    pub fn total(&self) -> i32 {
+        self.get_terms().iter().map(|(_, score)| score).sum()
+    }
+}
+
+impl std::fmt::Display for EvaluationBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, score) in self.get_terms() {
+            writeln!(f, "{0:>18} : {1:>6}", name, score)?;
+        }
+
+        write!(f, "{0:>18} : {1:>6}", "total", self.total())
+    }
+}
+
+impl Default for EvaluationProfile {
+    fn default() -> Self {
+        EvaluationProfile {
+            name: String::from("balanced"),
+            weights: EvaluationWeights::default(),
+        }
+    }
+}
+
+impl EvaluationProfile {
+    pub fn load_profiles<R: Read>(reader: R) -> Result<Vec<EvaluationProfile>, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    pub fn load_profiles_from_file() -> Result<Vec<EvaluationProfile>, String> {
+        let mut path = get_resource_dir();
+        path.push(EVALUATION_PROFILES_PATH.trim_start_matches('/'));
+        let file = std::fs::File::open(&path).map_err(|error| format!("{0:?} : {1}", path, error))?;
+        EvaluationProfile::load_profiles(file).map_err(|error| format!("{0:?} : {1}", path, error))
+    }
+
+    pub fn load_profiles_from_resources(ctx: &mut ggez::Context) -> ggez::GameResult<Vec<EvaluationProfile>> {
+        let file = ggez::filesystem::open(ctx, EVALUATION_PROFILES_PATH)?;
+        match EvaluationProfile::load_profiles(file) {
+            Ok(profiles) => Ok(profiles),
+            Err(error) => Err(ggez::GameError::ResourceLoadError(format!("{0} : {1}", EVALUATION_PROFILES_PATH, error))),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::board::*;
+use crate::brain::*;
+use crate::grid::*;
+use crate::cli::*;
+use crate::agent::*;
+
+pub const ENGINE_START_TIMEOUT: Duration = Duration::from_secs(5);
+// Time given to the engine on top of its move time before it is considered unresponsive.
+pub const ENGINE_MOVE_MARGIN: Duration = Duration::from_secs(2);
+pub const DEFAULT_ENGINE_MOVE_TIME: u64 = 1000;
+pub const ENGINE_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct ExternalEngineConfig {
+    pub command: String,
+    pub arguments: Vec<String>,
+    pub move_time: Duration,
+}
+
+enum EngineRequest {
+    Idle,
+    Searching(Instant),
+    Answered(Option<(usize, usize)>),
+}
+
+// An executable speaking the engine text protocol, see the engine binary.
+// Any error means the engine cannot be trusted anymore, the process is killed when dropped.
+pub struct ExternalEngine {
+    config: ExternalEngineConfig,
+    child: Child,
+    stdin: ChildStdin,
+    receiver: Receiver<String>,
+    request: EngineRequest,
+    ignored_answers: u32,
+}
+
+// The process is started with the first move asked, a failing engine resigns.
+pub struct ExternalEngineAgent {
+    config: ExternalEngineConfig,
+    engine: Option<ExternalEngine>,
+}
+
+impl ExternalEngineConfig {
+    pub fn new(command_line: &str, move_time: Duration) -> Option<ExternalEngineConfig> {
+        let mut tokens = command_line.split_whitespace().map(String::from);
+        let command = tokens.next()?;
+
+        Some(ExternalEngineConfig {
+            command,
+            arguments: tokens.collect(),
+            move_time,
+        })
+    }
+
+    // Reads --engine "<executable> [arguments]" and --engine-movetime MS.
+    pub fn from_command_line(command_line: &CommandLine) -> Option<ExternalEngineConfig> {
+        let move_time = Duration::from_millis(command_line.get_or("engine-movetime", DEFAULT_ENGINE_MOVE_TIME));
+        ExternalEngineConfig::new(command_line.get_str("engine")?, move_time)
+    }
+
+    pub fn name(&self) -> &str {
+        match std::path::Path::new(&self.command).file_stem().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => &self.command,
+        }
+    }
+}
+
+impl ExternalEngine {
+    pub fn start(config: &ExternalEngineConfig) -> Result<ExternalEngine, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|error| format!("cannot start {0} : {1}", config.command, error))?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let sent = match line {
+                    Ok(line) => sender.send(line).is_ok(),
+                    Err(_) => false,
+                };
+
+                if !sent {
+                    return;
+                }
+            }
+        });
+
+        let mut engine = ExternalEngine {
+            config: config.clone(),
+            child,
+            stdin,
+            receiver,
+            request: EngineRequest::Idle,
+            ignored_answers: 0,
+        };
+
+        engine.send("uci")?;
+        engine.wait_for("uciok")?;
+        engine.send("isready")?;
+        engine.wait_for("readyok")?;
+        Ok(engine)
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|error| format!("cannot write to the engine : {}", error))
+    }
+
+    fn wait_for(&self, expected: &str) -> Result<(), String> {
+        let deadline = Instant::now() + ENGINE_START_TIMEOUT;
+        loop {
+            match self.receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) if line.trim() == expected => return Ok(()),
+                Ok(_) => {},
+                Err(RecvTimeoutError::Timeout) => return Err(format!("{} not received in time", expected)),
+                Err(RecvTimeoutError::Disconnected) => return Err(String::from("the engine process exited")),
+            }
+        }
+    }
+
+    pub fn request_move(&mut self, board: &BoardState) -> Result<(), String> {
+        self.send(&format!("position notation {}", board.to_notation()))?;
+        self.send(&format!("go movetime {}", self.config.move_time.as_millis()))?;
+        self.request = EngineRequest::Searching(Instant::now());
+        Ok(())
+    }
+
+    // Reads the pending output of the engine, board being the position the current request was made for.
+    pub fn update(&mut self, board: &BoardState) -> Result<(), String> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(line) => self.read_line(&line, board)?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Err(String::from("the engine process exited")),
+            }
+        }
+
+        if let EngineRequest::Searching(start) = self.request {
+            if start.elapsed() > self.config.move_time + ENGINE_MOVE_MARGIN {
+                return Err(format!("no move after {:.1}s", start.elapsed().as_secs_f64()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_line(&mut self, line: &str, board: &BoardState) -> Result<(), String> {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() != Some("bestmove") {
+            return Ok(());
+        }
+
+        // Answer to a cancelled request.
+        if self.ignored_answers > 0 {
+            self.ignored_answers -= 1;
+            return Ok(());
+        }
+
+        if !matches!(self.request, EngineRequest::Searching(_)) {
+            return Err(format!("unexpected answer {}", line));
+        }
+
+        let play = match tokens.next() {
+            Some("none") => None,
+            Some(name) => match Grid::get_play_from_name(name) {
+                Some(play) if Brain::find_all_plays(board, board.current_player).contains(&play) => Some(play),
+                _ => return Err(format!("illegal move {}", name)),
+            },
+            None => return Err(String::from("bestmove without a move")),
+        };
+
+        self.request = EngineRequest::Answered(play);
+        Ok(())
+    }
+
+    // Returns the answer once it has been received, None standing for a resignation.
+    pub fn take_answer(&mut self) -> Option<Option<(usize, usize)>> {
+        match self.request {
+            EngineRequest::Answered(play) => {
+                self.request = EngineRequest::Idle;
+                Some(play)
+            },
+            _ => None,
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if let EngineRequest::Searching(_) = self.request {
+            // A stopped search still answers, that answer has to be skipped.
+            let _ = self.send("stop");
+            self.ignored_answers += 1;
+        }
+
+        self.request = EngineRequest::Idle;
+    }
+}
+
+impl ExternalEngineAgent {
+    pub fn new(config: ExternalEngineConfig) -> ExternalEngineAgent {
+        ExternalEngineAgent {
+            config,
+            engine: None,
+        }
+    }
+
+    fn try_choose_move(&mut self, board: &BoardState, limits: &AgentLimits) -> Result<Option<(usize, usize)>, String> {
+        if self.engine.is_none() {
+            self.engine = Some(ExternalEngine::start(&self.config)?);
+        }
+
+        let engine = self.engine.as_mut().unwrap();
+        engine.request_move(board)?;
+        loop {
+            if limits.is_cancelled() {
+                engine.cancel();
+                return Ok(None);
+            }
+
+            engine.update(board)?;
+            if let Some(play) = engine.take_answer() {
+                return Ok(play);
+            }
+
+            thread::sleep(ENGINE_POLL_INTERVAL);
+        }
+    }
+}
+
+impl Agent for ExternalEngineAgent {
+    fn name(&self) -> String {
+        self.config.name().to_owned()
+    }
+
+    fn choose_move(&mut self, board: &BoardState, limits: &AgentLimits) -> Option<(usize, usize)> {
+        match self.try_choose_move(board, limits) {
+            Ok(play) => play,
+            Err(error) => {
+                println!("{0} forfeits : {1}", self.config.name(), error);
+                self.engine = None;
+                None
+            },
+        }
+    }
+}
+
+impl Drop for ExternalEngine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        for _ in 0..10 {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
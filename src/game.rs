@@ -13,6 +13,8 @@ use crate::game_states::{
 
 pub const MAX_PAWN_NUMBER: usize = 4;
 pub const AI_PAUSE_TIME: f64 = 0.5_f64;
+pub const MIN_AI_PAUSE_TIME: f64 = 0.0625_f64;
+pub const MAX_AI_PAUSE_TIME: f64 = 4_f64;
 
 #[derive(Clone, Copy)]
 #[derive(PartialEq, Eq)]
@@ -29,6 +31,8 @@ pub struct DrawingContext {
 
 pub struct Game {
     game_state: GameState,
+    // The menu while a game is played, loaded once.
+    menu_state: Option<MenuState>,
     drawing_context: DrawingContext,
 }
 
@@ -44,7 +48,8 @@ impl PlayerSide {
 impl Game {
     pub fn new(ctx: &mut Context) -> GameResult<Game> {
         let game = Game {
-            game_state: GameState::MenuState(MenuState::new(ctx)),
+            game_state: GameState::MenuState(MenuState::new(ctx)?),
+            menu_state: None,
             drawing_context: DrawingContext { 
                 game_textures: GameTextures::new(ctx)?, 
                 time: timer::duration_to_f64(timer::time_since_start(ctx)),
@@ -63,9 +68,21 @@ impl ggez::event::EventHandler<GameError> for Game {
                     return Result::Ok(());
                 },
                 GameStateResult::NextState(next_state) => {
-                    self.game_state = next_state;
+                    if let GameState::MenuState(menu_state) = std::mem::replace(&mut self.game_state, next_state) {
+                        self.menu_state = Some(menu_state);
+                    }
+
                     return Result::Ok(());
                 },
+                GameStateResult::Menu => {
+                    let menu_state = match self.menu_state.take() {
+                        Some(menu_state) => menu_state,
+                        None => MenuState::new(ctx)?,
+                    };
+
+                    self.game_state = GameState::MenuState(menu_state);
+                    Result::Ok(())
+                },
             }
             Err(error) => {
                 return Result::Err(error);
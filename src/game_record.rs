@@ -0,0 +1,24 @@
+use crate::game::*;
+use crate::board::*;
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct GameRecord {
+    pub winner: PlayerSide,
+    pub top_hints: u32,
+    pub bottom_hints: u32,
+    // Seed given to the AIs, replaying the same moves against them with that seed gives the same game.
+    pub seed: Option<u64>,
+    // states[i] is the position in which moves[i] was played, the last state is the final position.
+    pub states: Vec<BoardState>,
+    pub moves: Vec<(usize, usize)>,
+}
+
+impl GameRecord {
+    pub fn get_hints(&self, side: PlayerSide) -> u32 {
+        match side {
+            PlayerSide::Top => self.top_hints,
+            PlayerSide::Bottom => self.bottom_hints,
+        }
+    }
+}
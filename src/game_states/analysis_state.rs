@@ -0,0 +1,189 @@
+use ggez::{
+    *,
+};
+
+use glam::*;
+
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+use crate::brain::*;
+use crate::shape_style::*;
+use crate::analysis::*;
+use crate::evaluation::*;
+use crate::ui::*;
+use crate::game_states::board_view::*;
+
+// Lists the best plays of a position, the selected one being previewed on the board one ply after the other.
+pub struct AnalysisState {
+    grid: Grid,
+    board: BoardState,
+    lines: Vec<SearchInfo>,
+    candidates_handle: Option<CandidatesHandle>,
+    selected: usize,
+    // Plies of the selected line already played on the board.
+    preview_plies: usize,
+
+    candidates: Vec<Button>,
+    back: Button,
+    was_key_pressed: bool,
+}
+
+pub enum AnalysisOption {
+    None,
+    Back,
+}
+
+impl AnalysisState {
+    pub fn new(board: BoardState) -> AnalysisState {
+        let grid_position = Vec2::new(70., 130.);
+        let mut config = AiConfig::new(SearchAlgorithm::AlphaBeta, EvaluationProfile::default());
+        config.depth = CANDIDATE_DEPTH;
+
+        let candidates = (0..CANDIDATE_COUNT)
+            .map(|index| Button::new("", graphics::Rect::new_i32(500, 130 + index as i32 * 50, 190, 40)))
+            .collect();
+
+        AnalysisState {
+            grid: Grid::new(0.3, grid_position, 50., 5.),
+            board,
+            lines: Vec::new(),
+            candidates_handle: Some(CandidatesHandle::start(board, config, CANDIDATE_COUNT)),
+            selected: 0,
+            preview_plies: 0,
+            candidates,
+            back: Button::new("Back", graphics::Rect::new_i32(560, 15, 120, 40)),
+            was_key_pressed: false,
+        }
+    }
+
+    fn get_selected_line(&self) -> &[(usize, usize)] {
+        match self.lines.get(self.selected) {
+            Some(info) => &info.pv,
+            None => &[],
+        }
+    }
+
+    fn get_preview_board(&self) -> BoardState {
+        self.get_selected_line()[..self.preview_plies].iter()
+            .fold(self.board, |board, play| board.make_move(play.0, play.1))
+    }
+
+    fn select(&mut self, index: usize) {
+        if index < self.lines.len() && index != self.selected {
+            self.selected = index;
+            self.preview_plies = 0;
+        }
+    }
+
+    pub fn update(&mut self, ctx: &mut Context) -> Result<AnalysisOption, GameError> {
+        if let Some(handle) = &self.candidates_handle {
+            // The lines of a deeper search may come in another order, the preview follows the selected play.
+            let selected_play = self.get_selected_line().first().copied();
+            self.lines = handle.get_lines();
+            if let Some(index) = self.lines.iter().position(|info| info.pv.first().copied() == selected_play) {
+                self.selected = index;
+            }
+
+            self.selected = self.selected.min(self.lines.len().max(1) - 1);
+            self.preview_plies = self.preview_plies.min(self.get_selected_line().len());
+            if handle.is_done() {
+                self.candidates_handle = None;
+            }
+        }
+
+        for (index, button) in self.candidates.iter_mut().enumerate() {
+            let label = match self.lines.get(index) {
+                Some(info) => format!("{0}. {1} ({2})", index + 1, Grid::get_play_name(info.pv[0]), info.score),
+                None => String::new(),
+            };
+
+            button.set_label(&label);
+        }
+
+        if self.back.update(ctx) || input::keyboard::is_key_pressed(ctx, event::KeyCode::Escape) {
+            return Ok(AnalysisOption::Back);
+        }
+
+        let mut clicked = None;
+        for (index, button) in self.candidates.iter_mut().enumerate() {
+            if button.update(ctx) {
+                clicked = Some(index);
+            }
+        }
+
+        if let Some(index) = clicked {
+            self.select(index);
+        }
+
+        let up = input::keyboard::is_key_pressed(ctx, event::KeyCode::Up);
+        let down = input::keyboard::is_key_pressed(ctx, event::KeyCode::Down);
+        let left = input::keyboard::is_key_pressed(ctx, event::KeyCode::Left);
+        let right = input::keyboard::is_key_pressed(ctx, event::KeyCode::Right);
+        let is_key_pressed = up || down || left || right;
+        if is_key_pressed && !self.was_key_pressed {
+            match (up, down, left, right) {
+                (true, _, _, _) if self.selected > 0 => self.select(self.selected - 1),
+                (_, true, _, _) => self.select(self.selected + 1),
+                (_, _, true, _) if self.preview_plies > 0 => self.preview_plies -= 1,
+                (_, _, _, true) if self.preview_plies < self.get_selected_line().len() => self.preview_plies += 1,
+                _ => {},
+            }
+        }
+
+        self.was_key_pressed = is_key_pressed;
+        Ok(AnalysisOption::None)
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, drawing_context: &mut DrawingContext) -> GameResult {
+        graphics::clear(ctx, graphics::Color::BLACK);
+
+        let line = self.get_selected_line().to_vec();
+        let last_play = match self.preview_plies {
+            0 => None,
+            plies => Some(line[plies - 1]),
+        };
+
+        draw_tiles(ctx, &self.grid, |index| {
+            match last_play {
+                Some(play) if play.0 == index || play.1 == index => ShapeStyle::Highlight,
+                _ => ShapeStyle::Base,
+            }
+        })?;
+
+        let board = self.get_preview_board();
+        draw_pawns(ctx, drawing_context, &self.grid, &board, -1);
+        draw_arrows(ctx, &self.grid, &line[self.preview_plies..])?;
+        draw_coordinates(ctx, &self.grid)?;
+
+        let state = match (&self.candidates_handle, self.lines.first()) {
+            (Some(_), Some(info)) => format!("searching, depth {}", info.depth),
+            (Some(_), None) => String::from("searching..."),
+            (None, Some(info)) => format!("depth {}", info.depth),
+            (None, None) => String::from("no legal play"),
+        };
+
+        let header = format!("Analysis : {0:?} to move, {1}", self.board.current_player, state);
+        graphics::draw(ctx, &graphics::Text::new(header), graphics::DrawParam::default().dest(Vec2::new(20_f32, 15_f32)))?;
+        let help = format!("Up / Down : candidate, Left / Right : preview {0}/{1}", self.preview_plies, line.len());
+        graphics::draw(ctx, &graphics::Text::new(help), graphics::DrawParam::default().dest(Vec2::new(20_f32, 40_f32)))?;
+
+        for (index, button) in self.candidates.iter().enumerate().take(self.lines.len()) {
+            button.draw(ctx)?;
+            if index == self.selected {
+                let rect = graphics::Mesh::new_rectangle(ctx, graphics::DrawMode::stroke(3_f32), graphics::Rect::new(496_f32, 126_f32 + index as f32 * 50_f32, 198_f32, 48_f32), graphics::Color::YELLOW)?;
+                graphics::draw(ctx, &rect, graphics::DrawParam::default())?;
+            }
+        }
+
+        // The plies already previewed are marked.
+        let line_text = line.iter().enumerate()
+            .map(|(ply, play)| format!("{0}{1}. {2}", match ply < self.preview_plies { true => "*", false => " " }, ply + 1, Grid::get_play_name(*play)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        graphics::draw(ctx, &graphics::Text::new(line_text), graphics::DrawParam::default().dest(Vec2::new(505_f32, 130_f32 + CANDIDATE_COUNT as f32 * 50_f32)))?;
+
+        self.back.draw(ctx)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use ggez::{
+    *,
+};
+
+use glam::*;
+
+use crate::game::*;
+use crate::grid::*;
+use crate::utils::*;
+use crate::shape_style::*;
+use crate::board::*;
+
+pub fn draw_tiles<F: Fn(usize) -> ShapeStyle>(ctx: &mut Context, grid: &Grid, get_style: F) -> GameResult {
+    let mut mesh_builder = graphics::MeshBuilder::new();
+    for index in 0..grid.tiles.len()
+    {
+        grid.tiles[index].build_mesh(get_style(index), &mut mesh_builder);
+    }
+
+    let mesh = mesh_builder.build(ctx)?;
+    graphics::draw(ctx, &mesh, graphics::DrawParam::default().dest(grid.position))
+}
+
+pub fn draw_pawns(ctx: &mut Context, drawing_context: &mut DrawingContext, grid: &Grid, board: &BoardState, selected_pawn: isize) {
+    for index in 0..NUMBER_OF_TILES {
+        if let Some(pawn) = board.tiles[index] {
+            pawn.draw(drawing_context, ctx, grid.tiles[index].position() + grid.position, 2_f32, selected_pawn == index as isize);
+        }
+    }
+}
+
+// Numbered arrows from the source to the destination of each play, fading along the line.
+pub fn draw_arrows(ctx: &mut Context, grid: &Grid, plays: &[(usize, usize)]) -> GameResult {
+    if plays.is_empty() {
+        return Ok(());
+    }
+
+    let mut mesh_builder = graphics::MeshBuilder::new();
+    let mut labels = Vec::new();
+    for (index, play) in plays.iter().enumerate() {
+        let source = grid.tiles[play.0].position();
+        let destination = grid.tiles[play.1].position();
+        let direction = (destination - source).normalize_or_zero();
+        let normal = Vec2::new(-direction.y, direction.x);
+        let head = destination - direction * 12_f32;
+        let alpha = 1_f32 - index as f32 / (plays.len() as f32 + 1_f32);
+        let color = graphics::Color::new(1_f32, 0.5_f32, 0_f32, alpha);
+
+        mesh_builder.line(&[source, head], 5_f32, color)?;
+        mesh_builder.triangles(&[head + normal * 10_f32, destination, head - normal * 10_f32], color)?;
+        labels.push((index + 1, (source + destination) / 2_f32));
+    }
+
+    let mesh = mesh_builder.build(ctx)?;
+    graphics::draw(ctx, &mesh, graphics::DrawParam::default().dest(grid.position))?;
+
+    for (number, position) in labels {
+        let label = graphics::Text::new(number.to_string());
+        let half_size = Vec2::new(label.width(ctx), label.height(ctx)) / 2_f32;
+        graphics::draw(ctx, &label, graphics::DrawParam::default().dest(grid.position + position - half_size))?;
+    }
+
+    Ok(())
+}
+
+pub fn draw_coordinates(ctx: &mut Context, grid: &Grid) -> GameResult {
+    let font_height = 24_f32;
+    for index in 0..TILES_ON_ROW {
+        let label = (('A' as u8 + index as u8) as char).to_string();
+        let mut label = graphics::Text::new(label);
+        label.set_font(graphics::Font::default(), graphics::PxScale{x: font_height, y: font_height});
+        let position = grid.position + Vec2::new(grid.width / (GRID_SIDE as f32 * 2_f32 ) * index as f32 - label.width(ctx) / 2_f32, grid.width + grid.scale);
+        graphics::draw(ctx, &label,graphics::DrawParam::default().dest(position))?;
+
+        let label = (TILES_ON_ROW - index - 1).to_string();
+        let mut label = graphics::Text::new(label);
+        label.set_font(graphics::Font::default(), graphics::PxScale{x: font_height, y: font_height});
+        let position = grid.position + Vec2::new(- grid.scale - label.width(ctx) / 2_f32, grid.width / (GRID_SIDE as f32 * 2_f32 ) * index as f32) - label.height(ctx) / 2_f32;
+        graphics::draw(ctx, &label,graphics::DrawParam::default().dest(position))?;
+    }
+
+    Ok(())
+}
@@ -10,17 +10,48 @@ use glam::*;
 
 use crate::{
         pawn::*,
-        DrawingContext,
+        game::*,
+        game_record::*,
+        ui::*,
     };
 
 
 pub struct GameOverState {
     pub winner_pawn : Pawn,
+    pub record: GameRecord,
+    review: Button,
+    menu: Button,
+}
+
+pub enum GameOverOption {
+    None,
+    Review,
+    Menu,
 }
 
 impl GameOverState {
-    pub fn update(&mut self, _ctx: &mut Context) -> Result<(), GameError> {
-        Ok(())
+    pub fn new(record: GameRecord) -> GameOverState {
+        GameOverState {
+            winner_pawn: Pawn{
+                player: record.winner,
+                table_index: 0,
+            },
+            record,
+            review: Button::new("Review", graphics::Rect::new_i32(200, 450, 150, 60)),
+            menu: Button::new("Menu", graphics::Rect::new_i32(380, 450, 150, 60)),
+        }
+    }
+
+    pub fn update(&mut self, ctx: &mut Context) -> Result<GameOverOption, GameError> {
+        if self.review.update(ctx) {
+            return Ok(GameOverOption::Review);
+        }
+
+        if self.menu.update(ctx) {
+            return Ok(GameOverOption::Menu);
+        }
+
+        Ok(GameOverOption::None)
     }
 
     pub fn draw(&mut self, ctx: &mut Context, drawing_context: &mut DrawingContext) -> Result<(), GameError> {
@@ -28,6 +59,17 @@ impl GameOverState {
         let winning_label = graphics::Text::new("Winner :");
         graphics::draw(ctx, &winning_label, graphics::DrawParam::default().dest(Vec2::new(350. - winning_label.width(ctx), 350. - winning_label.height(ctx) / 2.)))?;
         self.winner_pawn.draw(drawing_context, ctx, Vec2::new(375., 350.), 2., false);
+
+        let hints_label = graphics::Text::new(format!("Hints used : Bottom {0}, Top {1}", self.record.get_hints(PlayerSide::Bottom), self.record.get_hints(PlayerSide::Top)));
+        graphics::draw(ctx, &hints_label, graphics::DrawParam::default().dest(Vec2::new(350. - hints_label.width(ctx) / 2., 400.)))?;
+
+        if let Some(seed) = self.record.seed {
+            let seed_label = graphics::Text::new(format!("AI seed : {}", seed));
+            graphics::draw(ctx, &seed_label, graphics::DrawParam::default().dest(Vec2::new(350. - seed_label.width(ctx) / 2., 420.)))?;
+        }
+
+        self.review.draw(ctx)?;
+        self.menu.draw(ctx)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -12,6 +12,11 @@ use crate::brain::*;
 use crate::shape_style::*;
 use crate::board::*;
 use crate::pawn::*;
+use crate::search_handle::*;
+use crate::agent::*;
+use crate::game_record::*;
+use crate::ui::*;
+use crate::game_states::board_view::*;
 
 pub struct InGameState {
     grid: Grid,
@@ -27,17 +32,59 @@ pub struct InGameState {
     top_player_pawn : Pawn,
     bottom_player_pawn : Pawn,
     previous_states: Vec<BoardState>,
+    played_moves: Vec<(usize, usize)>,
     ai_timer: f64,
+    ai_search: Option<SearchHandle>,
+    ai_pause_time: f64,
+    is_undo: bool,
+    is_speed_key: bool,
+    show_ai_info: bool,
+    is_info_key: bool,
+    // Continuation expected by the last AI search, from the position it was searched in.
+    ai_info: Option<SearchInfo>,
+    // Highlights the pawns which can be captured without a recapture.
+    show_threats: bool,
+    is_threats_key: bool,
+
+    hint_button: Button,
+    // Searches for the human players only, the agents of the players are never asked for a hint.
+    hint_agent: SharedAgent,
+    hint_search: Option<SearchHandle>,
+    hint: Option<(usize, usize)>,
+    top_hints: u32,
+    bottom_hints: u32,
 }
 
-#[derive(PartialEq, Eq)]
-pub enum PlayerOption {
-    OnePlayer,
-    TwoPlayer,
+#[derive(Clone)]
+pub enum PlayerController {
+    Human,
+    Agent(SharedAgent),
+}
+
+#[derive(Clone)]
+pub struct PlayerOption {
+    pub top: PlayerController,
+    pub bottom: PlayerController,
+    pub seed: Option<u64>,
+    pub hint: AiConfig,
+}
+
+impl PlayerOption {
+    pub fn get_controller(&self, side: PlayerSide) -> &PlayerController {
+        match side {
+            PlayerSide::Top => &self.top,
+            PlayerSide::Bottom => &self.bottom,
+        }
+    }
+
+    pub fn has_human(&self) -> bool {
+        matches!(self.top, PlayerController::Human) || matches!(self.bottom, PlayerController::Human)
+    }
 }
 
 pub enum InGameResult {
-    Winner(PlayerSide),
+    GameOver(GameRecord),
+    Quit,
     None,
 }
 
@@ -46,15 +93,11 @@ impl InGameState {
         
         let grid_position = Vec2::new(120., 120.);
         let grid = Grid::new(0.3, grid_position, 60., 5.);
-        let mut game = InGameState{
+        let hint_agent = share_agent(player_option.hint.clone());
+        InGameState{
             grid,
             player_option,
-            board_state: BoardState {
-                tiles: [Option::None; NUMBER_OF_TILES],
-                current_player: PlayerSide::Bottom,
-                top_pawns: PawnArray::new(),
-                bottom_pawns: PawnArray::new(),
-            },
+            board_state: BoardState::starting_position(),
             was_pressed: false,
             is_pressed: false,
             hovered_tile: -1,
@@ -64,19 +107,24 @@ impl InGameState {
             top_player_pawn: Pawn {player: PlayerSide::Top, table_index: 0},
             bottom_player_pawn: Pawn{player: PlayerSide::Bottom, table_index: 0},
             previous_states: Vec::new(),
+            played_moves: Vec::new(),
             ai_timer: -1_f64,
-        };
-
-        game.board_state.add_pawn(TileCoord{x:3, y: 0}, PlayerSide::Top);
-        game.board_state.add_pawn(TileCoord{x:4, y: 0}, PlayerSide::Top);
-        game.board_state.add_pawn(TileCoord{x:5, y: 0}, PlayerSide::Top);
-        game.board_state.add_pawn(TileCoord{x:4, y: 1}, PlayerSide::Top);
-
-        game.board_state.add_pawn(TileCoord{x:3, y: 3}, PlayerSide::Bottom);
-        game.board_state.add_pawn(TileCoord{x:4, y: 4}, PlayerSide::Bottom);
-        game.board_state.add_pawn(TileCoord{x:5, y: 3}, PlayerSide::Bottom);
-
-        return game;
+            ai_search: None,
+            ai_pause_time: AI_PAUSE_TIME,
+            is_undo: false,
+            is_speed_key: false,
+            show_ai_info: false,
+            is_info_key: false,
+            ai_info: None,
+            show_threats: false,
+            is_threats_key: false,
+            hint_button: Button::new("Hint", graphics::Rect::new_i32(560, 15, 120, 40)),
+            hint_agent,
+            hint_search: None,
+            hint: None,
+            top_hints: 0,
+            bottom_hints: 0,
+        }
     }
 
     #[allow(dead_code)]
@@ -92,6 +140,102 @@ impl InGameState {
         self.selected_pawn = -1;
         self.possible_plays.clear();
     }
+
+    pub fn is_ai_turn(&self) -> bool {
+        !matches!(self.player_option.get_controller(self.board_state.current_player), PlayerController::Human)
+    }
+
+    pub fn cancel_ai_search(&mut self) {
+        if let Some(search) = self.ai_search.take() {
+            search.cancel();
+        }
+    }
+
+    // Starts the search of the agent to move if needed, its result is only given once the AI pause is over.
+    fn poll_ai_player(&mut self) -> SearchPoll {
+        let board = self.board_state;
+        let agent = match self.player_option.get_controller(board.current_player) {
+            PlayerController::Agent(agent) => agent,
+            PlayerController::Human => return SearchPoll::Pending,
+        };
+
+        if self.ai_search.is_none() {
+            self.ai_search = Some(SearchHandle::start(board, agent.clone()));
+        }
+
+        if let Some(info) = self.ai_search.as_ref().and_then(|search| search.get_progress()) {
+            self.ai_info = Some(info);
+        }
+
+        if self.ai_timer > 0_f64 {
+            return SearchPoll::Pending;
+        }
+
+        let result = match &self.ai_search {
+            Some(search) => search.poll(),
+            None => SearchPoll::Pending,
+        };
+
+        if let SearchPoll::Done(_) = result {
+            self.ai_search = None;
+        }
+
+        result
+    }
+
+    pub fn clear_hint(&mut self) {
+        self.hint_search = None;
+        self.hint = None;
+    }
+
+    fn request_hint(&mut self) {
+        if self.hint_search.is_some() || self.hint.is_some() {
+            return;
+        }
+
+        self.hint_search = Some(SearchHandle::start(self.board_state, self.hint_agent.clone()));
+        match self.board_state.current_player {
+            PlayerSide::Top => self.top_hints += 1,
+            PlayerSide::Bottom => self.bottom_hints += 1,
+        }
+    }
+
+    fn get_game_record(&self, winner: PlayerSide) -> GameRecord {
+        let mut states = self.previous_states.clone();
+        states.push(self.board_state);
+
+        GameRecord {
+            winner,
+            top_hints: self.top_hints,
+            bottom_hints: self.bottom_hints,
+            seed: self.player_option.seed,
+            states,
+            moves: self.played_moves.clone(),
+        }
+    }
+
+    // Goes back to the last position where a human had to play, or a single move when only AIs are playing.
+    pub fn undo(&mut self) {
+        self.cancel_ai_search();
+        self.ai_info = None;
+        self.clear_hint();
+        self.unselect_pawn();
+        while let Some(state) = self.previous_states.pop() {
+            self.played_moves.pop();
+            self.board_state = state;
+            if !self.is_ai_turn() || !self.player_option.has_human() {
+                break;
+            }
+        }
+    }
+
+    fn play_move(&mut self, source_index: usize, play_index: usize) {
+        self.clear_hint();
+        self.previous_states.push(self.board_state.clone());
+        self.played_moves.push((source_index, play_index));
+        self.board_state  = self.board_state.make_move(source_index, play_index);
+        self.ai_timer = self.ai_pause_time;
+    }
 }
 
 impl InGameState {
@@ -108,24 +252,72 @@ impl InGameState {
         self.was_pressed = self.is_pressed;
         self.is_pressed = input::mouse::button_pressed(ctx, event::MouseButton::Left);
         
-        let ai_play = self.board_state.current_player == PlayerSide::Top && self.player_option == PlayerOption::OnePlayer;
-        if ai_play {
+        if input::keyboard::is_key_pressed(ctx, event::KeyCode::Escape) {
+            self.cancel_ai_search();
+            return InGameResult::Quit;
+        }
+
+        let undo = input::keyboard::is_key_pressed(ctx, event::KeyCode::Z) && input::keyboard::is_mod_active(ctx, event::KeyMods::CTRL);
+        if undo && !self.is_undo {
+            self.undo();
+        }
+
+        self.is_undo = undo;
+
+        let faster = input::keyboard::is_key_pressed(ctx, event::KeyCode::Up);
+        let slower = input::keyboard::is_key_pressed(ctx, event::KeyCode::Down);
+        if !self.is_speed_key {
+            if faster {
+                self.ai_pause_time = (self.ai_pause_time / 2_f64).max(MIN_AI_PAUSE_TIME);
+            }
+            else if slower {
+                self.ai_pause_time = (self.ai_pause_time * 2_f64).min(MAX_AI_PAUSE_TIME);
+            }
+        }
+
+        self.is_speed_key = faster || slower;
+
+        let info_key = input::keyboard::is_key_pressed(ctx, event::KeyCode::I);
+        if info_key && !self.is_info_key {
+            self.show_ai_info = !self.show_ai_info;
+        }
+
+        self.is_info_key = info_key;
+
+        let threats_key = input::keyboard::is_key_pressed(ctx, event::KeyCode::T);
+        if threats_key && !self.is_threats_key {
+            self.show_threats = !self.show_threats;
+        }
+
+        self.is_threats_key = threats_key;
+
+        if self.is_ai_turn() {
             if self.ai_timer > 0_f64 {
                 let delta = timer::duration_to_f64(timer::delta(ctx));
                 self.ai_timer = self.ai_timer - delta;
-                if self.ai_timer <= 0_f64 {
-                    match Brain::search_best_play(&self.board_state, 10) {
-                        Some(best_play) => {
-                            self.previous_states.push(self.board_state.clone());
-                            self.board_state  = self.board_state.make_move(best_play.0, best_play.1);
-                        },
-
-                        None => {}
-                    }
-                }
+            }
+
+            match self.poll_ai_player() {
+                SearchPoll::Pending => {},
+                SearchPoll::Done(Some(best_play)) => self.play_move(best_play.0, best_play.1),
+                SearchPoll::Done(None) => return InGameResult::GameOver(self.get_game_record(self.board_state.current_player.reverse())),
             }
         }
         else {
+            if self.hint_button.update(ctx) {
+                self.request_hint();
+            }
+
+            let hint_result = match &self.hint_search {
+                Some(search) => search.poll(),
+                None => SearchPoll::Pending,
+            };
+
+            if let SearchPoll::Done(play) = hint_result {
+                self.hint_search = None;
+                self.hint = play;
+            }
+
             if !self.was_pressed && self.is_pressed {
             if self.hovered_tile > -1 {
                 if self.selected_pawn < 0 {
@@ -146,11 +338,7 @@ impl InGameState {
                     if self.hovered_tile != self.selected_pawn && self.possible_plays.contains(&(self.hovered_tile as usize)) {
                         let source_index = self.selected_pawn as usize;
                         self.unselect_pawn();
-
-                        self.previous_states.push(self.board_state.clone());
-                        self.board_state  = self.board_state.make_move(source_index, self.hovered_tile as usize);
-
-                        self.ai_timer = AI_PAUSE_TIME;
+                        self.play_move(source_index, self.hovered_tile as usize);
                     }
                     else {
                         self.unselect_pawn();
@@ -174,59 +362,59 @@ impl InGameState {
                 }
             }
         }
-
-        /*
-        let undo = input::keyboard::is_key_pressed(ctx, event::KeyCode::Z) && input::keyboard::is_mod_active(ctx, event::KeyMods::CTRL);
-        if undo && !self.is_undo {
-            match self.previous_states.pop() {
-                Some(state) => {self.board_state = state;}
-                None => {println!("History Empty");}
-            }
-        }
-
-        self.is_undo = undo;
-        // */
         }
 
-        if self.board_state.top_pawns.count == 0 {
-            return InGameResult::Winner(PlayerSide::Bottom);
-        }
-        else if self.board_state.bottom_pawns.count == 0 {
-            return InGameResult::Winner(PlayerSide::Top);
+        match self.board_state.get_winner() {
+            Some(winner) => InGameResult::GameOver(self.get_game_record(winner)),
+            None => InGameResult::None,
         }
-        
-        InGameResult::None
     }
 
     pub fn draw(&mut self, ctx: &mut Context, drawing_context: &mut DrawingContext) -> GameResult {
         graphics::clear(ctx, graphics::Color::BLACK);
 
-        let mut mesh_builder = graphics::MeshBuilder::new();
-        for index in 0..self.grid.tiles.len()
-        {
-            let tile = &self.grid.tiles[index];
-            let mut style = ShapeStyle::Base;
+        let mut hanging_pawns = Vec::new();
+        if self.show_threats {
+            let threat_map = self.board_state.get_threat_map();
+            hanging_pawns = threat_map.get_hanging_pawns(&self.board_state, PlayerSide::Top);
+            hanging_pawns.extend(threat_map.get_hanging_pawns(&self.board_state, PlayerSide::Bottom));
+        }
+
+        draw_tiles(ctx, &self.grid, |index| {
             if self.hovered_tile == index as isize {
                 if self.is_pressed {
-                    style = ShapeStyle::Press
-                }
-                else {
-                    style = ShapeStyle::Hovered
+                    return ShapeStyle::Press;
                 }
+
+                return ShapeStyle::Hovered;
             }
-            else if self.possible_plays.contains(&index) {
-                style = ShapeStyle::Highlight;
+
+            if self.possible_plays.contains(&index) {
+                return ShapeStyle::Highlight;
             }
 
-            tile.build_mesh(style, &mut mesh_builder);
-        }
+            match self.hint {
+                Some(hint) if hint.0 == index || hint.1 == index => ShapeStyle::Hint,
+                _ if hanging_pawns.contains(&index) => ShapeStyle::Threat,
+                _ => ShapeStyle::Base,
+            }
+        })?;
 
-        let mesh = mesh_builder.build(ctx).unwrap();
-        graphics::draw(ctx, &mesh, graphics::DrawParam::default().dest(self.grid.position))?; 
+        draw_pawns(ctx, drawing_context, &self.grid, &self.board_state, self.selected_pawn);
 
-        for index in 0..NUMBER_OF_TILES {
-            if let Some(pawn) = self.board_state.tiles[index] {
-                pawn.draw(drawing_context, ctx, self.grid.tiles[index].position() + self.grid.position, 2_f32, self.selected_pawn == index as isize);
+        // Preview of the way the selected pawn would take to a tile out of its reach.
+        if self.selected_pawn > -1 && self.hovered_tile > -1 && !self.possible_plays.contains(&(self.hovered_tile as usize)) {
+            if let Some(path) = self.board_state.get_pawn_path(self.selected_pawn as usize, self.hovered_tile as usize) {
+                let steps: Vec<(usize, usize)> = path.windows(2).map(|step| (step[0], step[1])).collect();
+                draw_arrows(ctx, &self.grid, &steps)?;
+            }
+        }
+
+        if self.show_ai_info {
+            if let Some(info) = &self.ai_info {
+                draw_arrows(ctx, &self.grid, &info.pv)?;
+                let label = graphics::Text::new(format!("AI depth {0}, score {1}, {2} nodes", info.depth, info.score, info.nodes));
+                graphics::draw(ctx, &label, graphics::DrawParam::default().dest(Vec2::new(10_f32, 655_f32)))?;
             }
         }
 
@@ -235,21 +423,18 @@ impl InGameState {
         let current_pawn = match self.board_state.current_player {PlayerSide::Bottom => self.bottom_player_pawn, PlayerSide::Top => self.top_player_pawn};
         current_pawn.draw(drawing_context, ctx, self.grid.position + Vec2::new(self.grid.width / 2. + 16_f32, -85.), 2_f32, false);
 
-        let font_height = 24_f32;
-        for index in 0..TILES_ON_ROW {
-            let label = (('A' as u8 + index as u8) as char).to_string();
-            let mut label = graphics::Text::new(label);
-            label.set_font(graphics::Font::default(), graphics::PxScale{x: font_height, y: font_height});
-            let position = self.grid.position + Vec2::new(self.grid.width / (GRID_SIDE as f32 * 2_f32 ) * index as f32 - label.width(ctx) / 2_f32, self.grid.width + self.grid.scale);
-            graphics::draw(ctx, &label,graphics::DrawParam::default().dest(position))?;
-
-            let label = (TILES_ON_ROW - index - 1).to_string();
-            let mut label = graphics::Text::new(label);
-            label.set_font(graphics::Font::default(), graphics::PxScale{x: font_height, y: font_height});
-            let position = self.grid.position + Vec2::new(- self.grid.scale - label.width(ctx) / 2_f32, self.grid.width / (GRID_SIDE as f32 * 2_f32 ) * index as f32) - label.height(ctx) / 2_f32;
-            graphics::draw(ctx, &label,graphics::DrawParam::default().dest(position))?;
+        if !self.is_ai_turn() {
+            self.hint_button.draw(ctx)?;
         }
 
+        if self.ai_search.is_some() || self.hint_search.is_some() {
+            let dots = ".".repeat((drawing_context.time * 3_f64) as usize % 4);
+            let label = graphics::Text::new(format!("Thinking{}", dots));
+            graphics::draw(ctx, &label, graphics::DrawParam::default().dest(self.grid.position + Vec2::new(self.grid.width / 2. + 48_f32, -85.)))?;
+        }
+
+        draw_coordinates(ctx, &self.grid)?;
+
         let mut label = "".to_owned();
         if self.hovered_tile > -1 {
             let coord = Grid::get_coord_from_index(self.hovered_tile as usize);
@@ -258,6 +443,17 @@ impl InGameState {
 
         let label = graphics::Text::new(label);
         graphics::draw(ctx, &label, graphics::DrawParam::default())?;
+
+        let threats_state = match self.show_threats { true => "On", false => "Off" };
+        let label = graphics::Text::new(format!("Threats : {} (T)", threats_state));
+        graphics::draw(ctx, &label, graphics::DrawParam::default().dest(Vec2::new(550_f32, 680_f32)))?;
+
+        if !matches!(self.player_option.top, PlayerController::Human) || !matches!(self.player_option.bottom, PlayerController::Human) {
+            let info_state = match self.show_ai_info { true => "On", false => "Off" };
+            let label = graphics::Text::new(format!("AI pause : {0:.2}s (Up / Down), AI info : {1} (I)", self.ai_pause_time, info_state));
+            graphics::draw(ctx, &label, graphics::DrawParam::default().dest(Vec2::new(10_f32, 680_f32)))?;
+        }
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -8,10 +8,42 @@ use crate::ui::*;
 use crate::grid::*;
 use crate::utils::Shape;
 use crate::pawn::*;
+use std::sync::Arc;
+use rand::Rng;
+
+use crate::evaluation::*;
+use crate::opening_book::*;
+use crate::tablebase::*;
+use crate::brain::*;
+use crate::cli::*;
+use crate::external_engine::*;
+use crate::agent::*;
+use crate::game_states::in_game_state::*;
 
 pub struct MenuState {
-    one_player: Button,
-    two_player: Button,
+    play: Button,
+    top_controller: Button,
+    bottom_controller: Button,
+    book_toggle: Button,
+    variety_toggle: Button,
+    algorithm_toggle: Button,
+
+    profiles: Vec<EvaluationProfile>,
+    opening_book: Option<Arc<OpeningBook>>,
+    use_book: bool,
+    // Used by every AI when found in the resources.
+    tablebase: Option<Arc<Tablebase>>,
+    engine: Option<ExternalEngineConfig>,
+    // AIs vary their play when a seed is set, --seed N on the command line replays the games of that seed.
+    seed: Option<u64>,
+    random_margin: i32,
+    threads: usize,
+    // Search of the AIs using a profile, only the alpha-beta one makes use of several threads.
+    algorithm: SearchAlgorithm,
+    // 0 stands for a human player, values up to profiles.len() for an AI using profiles[value - 1],
+    // followed by a random player and the external engine given on the command line if any.
+    top_choice: usize,
+    bottom_choice: usize,
 
     grid_mesh: graphics::Mesh,
     grid_position: glam::Vec2,
@@ -21,12 +53,11 @@ pub struct MenuState {
 
 pub enum MenuOption {
     None,
-    OnePlayer,
-    TwoPlayer,
+    Play(Box<PlayerOption>),
 }
 
 impl MenuState {
-    pub fn new(ctx: &mut ggez::Context) -> MenuState{
+    pub fn new(ctx: &mut ggez::Context) -> GameResult<MenuState>{
         let grid_position = glam::Vec2::new(120., 45.);
         let grid = Grid::new(0.3, grid_position, 60., 5.);
 
@@ -60,20 +91,152 @@ impl MenuState {
         pawns.push((Pawn{player: PlayerSide::Top, table_index: 0}, glam::Vec2::new(417_f32, 225_f32)));
         pawns.push((Pawn{player: PlayerSide::Bottom, table_index: 0}, glam::Vec2::new(537_f32, 225_f32)));
 
-        MenuState {
-            one_player: Button::new("1 Player", graphics::Rect::new_i32(200, 450, 150, 60)),
-            two_player: Button::new("2 Players", graphics::Rect::new_i32(380, 450, 150, 60)),
+        let profiles = match EvaluationProfile::load_profiles_from_resources(ctx) {
+            Ok(profiles) => profiles,
+            Err(error) => {
+                println!("Evaluation profiles unavailable : {}", error);
+                vec![EvaluationProfile::default()]
+            }
+        };
+
+        let command_line = CommandLine::from_env();
+        let engine = ExternalEngineConfig::from_command_line(&command_line);
+        let seed = command_line.get_str("seed").map(|_| command_line.get_or("seed", 0_u64));
+        let random_margin = command_line.get_or("ai-margin", DEFAULT_RANDOM_MARGIN);
+        let threads = command_line.get_or("threads", 1);
+        let algorithm = command_line.get_str("ai-algorithm").and_then(SearchAlgorithm::from_name).unwrap_or(SearchAlgorithm::AlphaBeta);
+        let top_choice = profiles.len().min(1);
+        let bottom_choice = 0;
+        let top_label = MenuState::get_controller_label(&profiles, &engine, PlayerSide::Top, top_choice);
+        let bottom_label = MenuState::get_controller_label(&profiles, &engine, PlayerSide::Bottom, bottom_choice);
+
+        let opening_book = match OpeningBook::load_from_resources(ctx) {
+            Ok(book) => Some(Arc::new(book)),
+            Err(error) => {
+                println!("Opening book unavailable : {}", error);
+                None
+            }
+        };
+
+        let use_book = opening_book.is_some();
+        let tablebase = match Tablebase::load_from_resources(ctx) {
+            Ok(tablebase) => Some(Arc::new(tablebase)),
+            Err(error) => {
+                println!("Tablebase unavailable : {}", error);
+                None
+            }
+        };
+
+        Ok(MenuState {
+            play: Button::new("Play", graphics::Rect::new_i32(200, 530, 150, 60)),
+            book_toggle: Button::new(MenuState::get_book_label(use_book), graphics::Rect::new_i32(380, 530, 150, 60)),
+            variety_toggle: Button::new(&MenuState::get_variety_label(seed), graphics::Rect::new_i32(200, 610, 330, 60)),
+            algorithm_toggle: Button::new(MenuState::get_algorithm_label(algorithm), graphics::Rect::new_i32(200, 370, 330, 60)),
+            top_controller: Button::new(&top_label, graphics::Rect::new_i32(200, 450, 150, 60)),
+            bottom_controller: Button::new(&bottom_label, graphics::Rect::new_i32(380, 450, 150, 60)),
+            profiles,
+            opening_book,
+            use_book,
+            tablebase,
+            engine,
+            seed,
+            random_margin,
+            threads,
+            algorithm,
+            top_choice,
+            bottom_choice,
             grid_mesh: mesh,
             grid_position,
             letters,
             pawns,
+        })
+    }
+
+    fn get_controller_label(profiles: &[EvaluationProfile], engine: &Option<ExternalEngineConfig>, side: PlayerSide, choice: usize) -> String {
+        let side = match side { PlayerSide::Top => "Top", PlayerSide::Bottom => "Bottom" };
+        match (choice, engine) {
+            (0, _) => format!("{} : Human", side),
+            _ if choice <= profiles.len() => format!("{0} : AI {1}", side, profiles[choice - 1].name),
+            _ if choice == profiles.len() + 1 => format!("{} : Random", side),
+            (_, Some(engine)) => format!("{0} : {1}", side, engine.name()),
+            (_, None) => panic!("Invalid player choice {}", choice),
+        }
+    }
+
+    fn get_book_label(use_book: bool) -> &'static str {
+        match use_book {
+            true => "Book : On",
+            false => "Book : Off",
         }
     }
 
+    fn get_algorithm_label(algorithm: SearchAlgorithm) -> &'static str {
+        match algorithm {
+            SearchAlgorithm::Greedy => "AI search : Greedy",
+            SearchAlgorithm::AlphaBeta => "AI search : Alpha-beta",
+        }
+    }
+
+    fn get_variety_label(seed: Option<u64>) -> String {
+        match seed {
+            Some(seed) => format!("AI variety : seed {}", seed),
+            None => String::from("AI variety : Off"),
+        }
+    }
+
+    fn get_controller(&self, choice: usize) -> PlayerController {
+        if choice == 0 {
+            return PlayerController::Human;
+        }
+
+        if choice == self.profiles.len() + 1 {
+            let seed = match self.seed { Some(seed) => seed, None => rand::thread_rng().gen() };
+            return PlayerController::Agent(share_agent(RandomAgent::new(seed)));
+        }
+
+        if let (Some(engine), true) = (&self.engine, choice > self.profiles.len()) {
+            return PlayerController::Agent(share_agent(ExternalEngineAgent::new(engine.clone())));
+        }
+
+        let mut config = AiConfig::new(self.algorithm, self.profiles[choice - 1].clone());
+        if self.use_book {
+            config.opening_book = self.opening_book.clone();
+        }
+
+        config.tablebase = self.tablebase.clone();
+        config.seed = self.seed;
+        config.random_margin = self.random_margin;
+        config.threads = self.threads;
+
+        PlayerController::Agent(share_agent(config))
+    }
+
+    // Hints come from their own AI, without the seed so that asking for one does not change the game replayed from it.
+    fn get_hint_config(&self) -> AiConfig {
+        let profile = match self.profiles.iter().find(|profile| profile.name == "balanced") {
+            Some(profile) => profile.clone(),
+            None => EvaluationProfile::default(),
+        };
+
+        let mut config = AiConfig::new(self.algorithm, profile);
+        config.threads = self.threads;
+        config.tablebase = self.tablebase.clone();
+        config
+    }
+
+    fn next_choice(&self, choice: usize) -> usize {
+        let engine_count = match self.engine { Some(_) => 1, None => 0 };
+        (choice + 1) % (self.profiles.len() + engine_count + 2)
+    }
+
     pub fn draw(&mut self, ctx: &mut Context, drawing_context: &mut DrawingContext) -> Result<(), GameError> {
         graphics::clear(ctx, graphics::Color::BLACK);
-        self.one_player.draw(ctx)?;
-        self.two_player.draw(ctx)?;
+        self.play.draw(ctx)?;
+        self.top_controller.draw(ctx)?;
+        self.bottom_controller.draw(ctx)?;
+        self.book_toggle.draw(ctx)?;
+        self.variety_toggle.draw(ctx)?;
+        self.algorithm_toggle.draw(ctx)?;
 
         graphics::draw(ctx, &self.grid_mesh, graphics::DrawParam::default().dest(self.grid_position))?; 
 
@@ -89,12 +252,46 @@ impl MenuState {
     }
 
     pub fn update(&mut self, ctx: &Context) -> Result<MenuOption, GameError> {
-        if self.one_player.update(ctx) {
-            return Ok( MenuOption::OnePlayer);
+        if self.play.update(ctx) {
+            return Ok( MenuOption::Play(Box::new(PlayerOption {
+                top: self.get_controller(self.top_choice),
+                bottom: self.get_controller(self.bottom_choice),
+                seed: self.seed,
+                hint: self.get_hint_config(),
+            })));
         }
 
-        if self.two_player.update(ctx) {
-            return Ok( MenuOption::TwoPlayer);
+        if self.top_controller.update(ctx) {
+            self.top_choice = self.next_choice(self.top_choice);
+            self.top_controller.set_label(&MenuState::get_controller_label(&self.profiles, &self.engine, PlayerSide::Top, self.top_choice));
+        }
+
+        if self.bottom_controller.update(ctx) {
+            self.bottom_choice = self.next_choice(self.bottom_choice);
+            self.bottom_controller.set_label(&MenuState::get_controller_label(&self.profiles, &self.engine, PlayerSide::Bottom, self.bottom_choice));
+        }
+
+        if self.book_toggle.update(ctx) && self.opening_book.is_some() {
+            self.use_book = !self.use_book;
+            self.book_toggle.set_label(MenuState::get_book_label(self.use_book));
+        }
+
+        if self.variety_toggle.update(ctx) {
+            self.seed = match self.seed {
+                Some(_) => None,
+                None => Some(rand::thread_rng().gen()),
+            };
+
+            self.variety_toggle.set_label(&MenuState::get_variety_label(self.seed));
+        }
+
+        if self.algorithm_toggle.update(ctx) {
+            self.algorithm = match self.algorithm {
+                SearchAlgorithm::Greedy => SearchAlgorithm::AlphaBeta,
+                SearchAlgorithm::AlphaBeta => SearchAlgorithm::Greedy,
+            };
+
+            self.algorithm_toggle.set_label(MenuState::get_algorithm_label(self.algorithm));
         }
 
         Ok( MenuOption::None)
@@ -1,12 +1,14 @@
 pub mod menu_state;
 pub mod in_game_state;
 pub mod game_over_state;
-
-use crate::pawn::*;
+pub mod review_state;
+pub mod board_view;
+pub mod analysis_state;
 
 use menu_state::*;
 use in_game_state::*;
 use game_over_state::*;
+use review_state::*;
 
 use crate::{game};
 
@@ -14,11 +16,14 @@ pub enum GameState {
     MenuState(MenuState),
     InGame(InGameState),
     GameOver(GameOverState),
+    Review(ReviewState),
 }
 
 pub enum GameStateResult {
     None,
     NextState(GameState),
+    // Back to the menu the game was started from, with the choices made in it and the resources it loaded.
+    Menu,
 }
 
 impl GameState {
@@ -30,12 +35,9 @@ impl GameState {
                         MenuOption::None => {
                             Ok(GameStateResult::None)
                         },
-                        MenuOption::OnePlayer => {
-                            Ok(GameStateResult::NextState(GameState::InGame(InGameState::new(PlayerOption::OnePlayer))))
+                        MenuOption::Play(player_option) => {
+                            Ok(GameStateResult::NextState(GameState::InGame(InGameState::new(*player_option))))
                         },
-                        MenuOption::TwoPlayer => {
-                            Ok(GameStateResult::NextState(GameState::InGame(InGameState::new(PlayerOption::TwoPlayer))))
-                        }
                     }
                 }
                 Err(error)=> {
@@ -46,20 +48,32 @@ impl GameState {
                 InGameResult::None => {
                     Ok(GameStateResult::None)
                 }
-                InGameResult::Winner(winner) => {
-                    Ok( GameStateResult::NextState(GameState::GameOver(GameOverState{
-                        winner_pawn: Pawn{
-                            player: winner,
-                            table_index: 0,
-                        }
-                    })))
+                InGameResult::Quit => {
+                    Ok(GameStateResult::Menu)
+                }
+                InGameResult::GameOver(record) => {
+                    Ok( GameStateResult::NextState(GameState::GameOver(GameOverState::new(record))))
+                }
+            },
+            GameState::GameOver(state) => match state.update(ctx)? {
+                GameOverOption::None => {
+                    Ok(GameStateResult::None)
+                }
+                GameOverOption::Review => {
+                    Ok(GameStateResult::NextState(GameState::Review(ReviewState::new(state.record.clone()))))
+                }
+                GameOverOption::Menu => {
+                    Ok(GameStateResult::Menu)
+                }
+            },
+            GameState::Review(state) => match state.update(ctx)? {
+                ReviewOption::None => {
+                    Ok(GameStateResult::None)
+                }
+                ReviewOption::Menu => {
+                    Ok(GameStateResult::Menu)
                 }
             },
-            GameState::GameOver(state) => 
-            { 
-                state.update(ctx)?;
-                Ok(GameStateResult::None)
-            }
         }
     }
 
@@ -68,6 +82,7 @@ impl GameState {
             GameState::MenuState(state) => state.draw(ctx, drawing_context),
             GameState::InGame(state) => state.draw(ctx, drawing_context),
             GameState::GameOver(state) => state.draw(ctx, drawing_context),
+            GameState::Review(state) => state.draw(ctx, drawing_context),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+use ggez::{
+    *,
+};
+
+use glam::*;
+
+use crate::game::*;
+use crate::grid::*;
+use crate::shape_style::*;
+use crate::analysis::*;
+use crate::evaluation::*;
+use crate::game_record::*;
+use crate::ui::*;
+use crate::game_states::board_view::*;
+use crate::game_states::analysis_state::*;
+
+pub struct ReviewState {
+    grid: Grid,
+    record: GameRecord,
+    analyses: Vec<MoveAnalysis>,
+    analysis_handle: Option<AnalysisHandle>,
+    step: usize,
+    // The position of the step being studied, shown instead of the review until going back.
+    analysis_state: Option<AnalysisState>,
+
+    previous: Button,
+    next: Button,
+    analyse: Button,
+    menu: Button,
+    was_key_pressed: bool,
+}
+
+pub enum ReviewOption {
+    None,
+    Menu,
+}
+
+impl ReviewState {
+    pub fn new(record: GameRecord) -> ReviewState {
+        let grid_position = Vec2::new(120., 120.);
+        let analysis_handle = Some(AnalysisHandle::start(&record, EvaluationWeights::default()));
+
+        ReviewState {
+            grid: Grid::new(0.3, grid_position, 60., 5.),
+            record,
+            analyses: Vec::new(),
+            analysis_handle,
+            step: 0,
+            analysis_state: None,
+            previous: Button::new("<", graphics::Rect::new_i32(20, 15, 50, 40)),
+            next: Button::new(">", graphics::Rect::new_i32(80, 15, 50, 40)),
+            analyse: Button::new("Analyse", graphics::Rect::new_i32(440, 15, 110, 40)),
+            menu: Button::new("Menu", graphics::Rect::new_i32(560, 15, 120, 40)),
+            was_key_pressed: false,
+        }
+    }
+
+    fn move_count(&self) -> usize {
+        self.record.moves.len()
+    }
+
+    fn get_step_label(&self) -> (String, String) {
+        if self.step >= self.move_count() {
+            return (format!("Final position, {:?} wins", self.record.winner), self.get_summary());
+        }
+
+        let played = self.record.moves[self.step];
+        let player = self.record.states[self.step].current_player;
+        let header = format!("Move {0}/{1} : {2:?} plays {3}", self.step + 1, self.move_count(), player, Grid::get_play_name(played));
+        match self.analyses.get(self.step) {
+            Some(analysis) => {
+                let mut details = format!("{0} ({1:+})", analysis.quality.name(), -analysis.get_score_loss());
+                if analysis.best != analysis.played {
+                    details.push_str(&format!(", best was {0} ({1})", Grid::get_play_name(analysis.best), analysis.best_score));
+                }
+
+                (header, details)
+            },
+            None => (header, format!("Analysing... {0}/{1}", self.analyses.len(), self.move_count())),
+        }
+    }
+
+    fn get_summary(&self) -> String {
+        if self.analyses.len() < self.move_count() {
+            return format!("Analysing... {0}/{1}", self.analyses.len(), self.move_count());
+        }
+
+        let mut summary = String::new();
+        for side in [PlayerSide::Bottom, PlayerSide::Top] {
+            let count = |quality: MoveQuality| self.analyses.iter().filter(|analysis| analysis.player == side && analysis.quality == quality).count();
+            summary.push_str(&format!("{0:?} : {1} inaccuracies, {2} mistakes, {3} blunders\n", side, count(MoveQuality::Inaccuracy), count(MoveQuality::Mistake), count(MoveQuality::Blunder)));
+        }
+
+        summary
+    }
+
+    pub fn update(&mut self, ctx: &mut Context) -> Result<ReviewOption, GameError> {
+        if let Some(handle) = &self.analysis_handle {
+            self.analyses.extend(handle.poll());
+            if self.analyses.len() >= self.move_count() {
+                self.analysis_handle = None;
+            }
+        }
+
+        if let Some(analysis_state) = &mut self.analysis_state {
+            match analysis_state.update(ctx)? {
+                AnalysisOption::None => {},
+                AnalysisOption::Back => {
+                    self.analysis_state = None;
+                    self.was_key_pressed = true;
+                },
+            }
+
+            return Ok(ReviewOption::None);
+        }
+
+        // Keys only act when pressed, so that a key still held from the analysis does nothing here.
+        let left = input::keyboard::is_key_pressed(ctx, event::KeyCode::Left);
+        let right = input::keyboard::is_key_pressed(ctx, event::KeyCode::Right);
+        let analyse = input::keyboard::is_key_pressed(ctx, event::KeyCode::A);
+        let escape = input::keyboard::is_key_pressed(ctx, event::KeyCode::Escape);
+        let was_key_pressed = self.was_key_pressed;
+        self.was_key_pressed = left || right || analyse || escape;
+
+        if self.analyse.update(ctx) || (analyse && !was_key_pressed) {
+            let board = self.record.states[self.step.min(self.record.states.len() - 1)];
+            self.analysis_state = Some(AnalysisState::new(board));
+            return Ok(ReviewOption::None);
+        }
+
+        if self.menu.update(ctx) || (escape && !was_key_pressed) {
+            return Ok(ReviewOption::Menu);
+        }
+
+        let go_previous = self.previous.update(ctx) || (left && !was_key_pressed);
+        let go_next = self.next.update(ctx) || (right && !was_key_pressed);
+
+        if go_previous && self.step > 0 {
+            self.step -= 1;
+        }
+
+        if go_next && self.step < self.move_count() {
+            self.step += 1;
+        }
+
+        Ok(ReviewOption::None)
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, drawing_context: &mut DrawingContext) -> GameResult {
+        if let Some(analysis_state) = &mut self.analysis_state {
+            return analysis_state.draw(ctx, drawing_context);
+        }
+
+        graphics::clear(ctx, graphics::Color::BLACK);
+
+        let played = self.record.moves.get(self.step).copied();
+        let best = self.analyses.get(self.step).map(|analysis| analysis.best);
+        draw_tiles(ctx, &self.grid, |index| {
+            match (played, best) {
+                (Some(played), _) if played.0 == index || played.1 == index => ShapeStyle::Highlight,
+                (_, Some(best)) if best.0 == index || best.1 == index => ShapeStyle::Hint,
+                _ => ShapeStyle::Base,
+            }
+        })?;
+
+        let board = &self.record.states[self.step.min(self.record.states.len() - 1)];
+        let selected_pawn = match played { Some(played) => played.0 as isize, None => -1 };
+        draw_pawns(ctx, drawing_context, &self.grid, board, selected_pawn);
+        draw_coordinates(ctx, &self.grid)?;
+
+        let (header, details) = self.get_step_label();
+        graphics::draw(ctx, &graphics::Text::new(header), graphics::DrawParam::default().dest(Vec2::new(145_f32, 15_f32)))?;
+        graphics::draw(ctx, &graphics::Text::new(details), graphics::DrawParam::default().dest(Vec2::new(145_f32, 40_f32)))?;
+
+        self.previous.draw(ctx)?;
+        self.next.draw(ctx)?;
+        self.analyse.draw(ctx)?;
+        self.menu.draw(ctx)?;
+        Ok(())
+    }
+}
@@ -139,7 +139,82 @@ impl Grid{
         return result;
     }
 
-    #[allow(dead_code)]
+    // Tiles a pawn can move to from the tile, whatever stands on them.
+    pub fn get_neighbours(tile_index: usize) -> Vec<usize> {
+        let coord = Grid::get_coord_from_index(tile_index);
+        let mut neighbours = Vec::new();
+        
+        match Grid::get_tile_shape_from_index(tile_index) {
+            TileShape::Quad => {
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y - 1}) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y - 1}) {neighbours.push(index)};
+            },
+
+            TileShape::Octo => {
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 2, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 2, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x    , y: coord.y + 1}) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x    , y: coord.y - 1}) {neighbours.push(index)};
+
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y }) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x + 1, y: coord.y + 1}) {neighbours.push(index)};
+                if let Some(index) = Grid::get_index_from_coord(TileCoord{ x: coord.x - 1, y: coord.y + 1}) {neighbours.push(index)};
+            },
+        }
+
+        return neighbours;
+    }
+
+    // The grid is left-right symmetric around its middle column.
+    pub fn get_mirrored_index(tile_index: usize) -> usize {
+        let coord = Grid::get_coord_from_index(tile_index);
+        Grid::get_index_from_coord_unsafe(TileCoord { x: TILES_ON_ROW as i32 - 1 - coord.x, y: coord.y })
+    }
+
+    // Upside down, the first row of quad tiles becomes the last one, octo tiles staying between the same two rows of quad tiles.
+    pub fn get_flipped_index(tile_index: usize) -> usize {
+        let coord = Grid::get_coord_from_index(tile_index);
+        let last_row = match Grid::get_tile_shape(coord) { TileShape::Quad => GRID_SIDE as i32, TileShape::Octo => GRID_SIDE as i32 - 1 };
+        Grid::get_index_from_coord_unsafe(TileCoord { x: coord.x, y: last_row - coord.y })
+    }
+
+    // Names follow the labels drawn around the board : a column letter and a row number counted in half tiles from the bottom.
+    pub fn get_tile_name(index: usize) -> String {
+        let coord = Grid::get_coord_from_index(index);
+        let half_row = match Grid::get_tile_shape(coord) { TileShape::Quad => coord.y * 2, TileShape::Octo => coord.y * 2 + 1 };
+        let column = (b'A' + coord.x as u8) as char;
+        format!("{0}{1}", column, TILES_ON_ROW as i32 - 1 - half_row)
+    }
+
+    pub fn get_index_from_name(name: &str) -> Option<usize> {
+        let mut characters = name.chars();
+        let column = characters.next()?.to_ascii_uppercase();
+        if !column.is_ascii_uppercase() {
+            return None;
+        }
+
+        let row: i32 = characters.as_str().parse().ok()?;
+        let x = column as i32 - 'A' as i32;
+        let half_row = TILES_ON_ROW as i32 - 1 - row;
+        if half_row < 0 || x % 2 != half_row % 2 {
+            return None;
+        }
+
+        Grid::get_index_from_coord(TileCoord { x, y: half_row / 2 })
+    }
+
+    pub fn get_play_name(play: (usize, usize)) -> String {
+        format!("{0}-{1}", Grid::get_tile_name(play.0), Grid::get_tile_name(play.1))
+    }
+
+    pub fn get_play_from_name(name: &str) -> Option<(usize, usize)> {
+        let (source, destination) = name.split_once('-')?;
+        Some((Grid::get_index_from_name(source)?, Grid::get_index_from_name(destination)?))
+    }
+
     pub fn get_tile_shape(coord: TileCoord) -> TileShape {
         if coord.x % 2 == 0 {
             return TileShape::Quad;
@@ -149,13 +224,9 @@ impl Grid{
         }
     }
 
+    // The last row only has quad tiles, packed at the start of its row of indexes.
     pub fn get_tile_shape_from_index(tile_index: usize) -> TileShape {
-        if tile_index % TILES_ON_ROW % 2 == 0 {
-            return TileShape::Quad;
-        }
-        else {
-            return TileShape::Octo;
-        }
+        Grid::get_tile_shape(Grid::get_coord_from_index(tile_index))
     }
 
     pub fn get_tile_at(&self, position: Vec2) -> isize{
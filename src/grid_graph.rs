@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+
+// Number of moves to reach each tile, None for the tiles out of reach.
+pub type DistanceMap = [Option<u32>; NUMBER_OF_TILES];
+
+// The tiles seen as a graph, two tiles being linked when a pawn can move from one to the other.
+// Blocked tiles can be reached but not crossed, as a pawn can capture on a tile but not go through it.
+impl Grid {
+    pub fn get_distances<F: Fn(usize) -> bool>(from: usize, is_blocked: F) -> DistanceMap {
+        let mut distances = [None; NUMBER_OF_TILES];
+        distances[from] = Some(0);
+        let mut queue = VecDeque::from([from]);
+        while let Some(tile_index) = queue.pop_front() {
+            let distance = distances[tile_index].unwrap();
+            if tile_index != from && is_blocked(tile_index) {
+                continue;
+            }
+
+            for neighbour in Grid::get_neighbours(tile_index) {
+                if distances[neighbour].is_none() {
+                    distances[neighbour] = Some(distance + 1);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    // The tiles from the first one to the last one, both included.
+    pub fn get_path<F: Fn(usize) -> bool>(from: usize, to: usize, is_blocked: F) -> Option<Vec<usize>> {
+        // Searched from the end so that each step leads to a tile one move closer to it.
+        let distances = Grid::get_distances(to, |tile_index| tile_index != from && is_blocked(tile_index));
+        let mut path = vec![from];
+        let mut tile_index = from;
+        let mut distance = distances[from]?;
+        while distance > 0 {
+            tile_index = Grid::get_neighbours(tile_index).into_iter()
+                .find(|neighbour| distances[*neighbour] == Some(distance - 1) && (*neighbour == to || !is_blocked(*neighbour)))?;
+            distance -= 1;
+            path.push(tile_index);
+        }
+
+        Some(path)
+    }
+
+    // Groups of tiles linked through tiles which are not blocked, blocked tiles belonging to none.
+    pub fn get_regions<F: Fn(usize) -> bool>(is_blocked: F) -> Vec<Vec<usize>> {
+        let mut regions: Vec<Vec<usize>> = Vec::new();
+        let mut visited = [false; NUMBER_OF_TILES];
+        for start in 0..NUMBER_OF_TILES {
+            if visited[start] || is_blocked(start) {
+                continue;
+            }
+
+            let mut region = Vec::new();
+            let mut stack = vec![start];
+            visited[start] = true;
+            while let Some(tile_index) = stack.pop() {
+                region.push(tile_index);
+                for neighbour in Grid::get_neighbours(tile_index) {
+                    if !visited[neighbour] && !is_blocked(neighbour) {
+                        visited[neighbour] = true;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+
+            region.sort_unstable();
+            regions.push(region);
+        }
+
+        regions
+    }
+}
+
+impl BoardState {
+    // Moves the pawn on the tile needs to reach each tile, going around every other pawn
+    // and only stopping on the enemy ones.
+    pub fn get_pawn_distances(&self, tile_index: usize) -> DistanceMap {
+        let mut distances = Grid::get_distances(tile_index, |other_index| self.tiles[other_index].is_some());
+        if let Some(pawn) = self.tiles[tile_index] {
+            let friends = match pawn.player { PlayerSide::Top => &self.top_pawns, PlayerSide::Bottom => &self.bottom_pawns };
+            for friend_tile in &friends.tile_indexes[..friends.count] {
+                if *friend_tile != tile_index {
+                    distances[*friend_tile] = None;
+                }
+            }
+        }
+
+        distances
+    }
+
+    // Shortest way for the pawn on the first tile to the second one, under the same rules as get_pawn_distances.
+    pub fn get_pawn_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let player = self.tiles[from]?.player;
+        match self.tiles[to] {
+            Some(pawn) if pawn.player == player => None,
+            _ => Grid::get_path(from, to, |tile_index| self.tiles[tile_index].is_some()),
+        }
+    }
+
+    pub fn get_distance_to_nearest_enemy(&self, tile_index: usize) -> Option<u32> {
+        let player = self.tiles[tile_index]?.player;
+        let enemies = match player { PlayerSide::Top => &self.bottom_pawns, PlayerSide::Bottom => &self.top_pawns };
+        let distances = self.get_pawn_distances(tile_index);
+        enemies.tile_indexes[..enemies.count].iter().filter_map(|enemy_tile| distances[*enemy_tile]).min()
+    }
+
+    // Groups of empty tiles a pawn can cross from one to another.
+    pub fn get_empty_regions(&self) -> Vec<Vec<usize>> {
+        Grid::get_regions(|tile_index| self.tiles[tile_index].is_some())
+    }
+}
@@ -0,0 +1,26 @@
+pub mod board;
+pub mod pawn;
+pub mod shape_style;
+pub mod tiles;
+pub mod utils;
+pub mod grid;
+pub mod grid_graph;
+pub mod symmetry;
+pub mod threat_map;
+pub mod brain;
+pub mod agent;
+pub mod search_handle;
+pub mod external_engine;
+pub mod evaluation;
+pub mod opening_book;
+pub mod tablebase;
+pub mod solver;
+pub mod textures;
+pub mod self_play;
+pub mod game_record;
+pub mod analysis;
+pub mod cli;
+
+pub mod game_states;
+pub mod game;
+pub mod ui;
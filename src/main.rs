@@ -1,32 +1,12 @@
 use ggez::{*};
 
-mod board;
-mod pawn;
-mod shape_style;
-mod tiles;
-mod utils;
-mod grid;
-mod brain;
-mod textures;
-
-mod game_states;
-mod game;
-mod ui;
-
-use crate::game::*;
-
-use std::{env, path};
+use octo_chess::game::*;
+use octo_chess::cli::get_resource_dir;
 
 fn main(){
 
 
-    let resource_dir = if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-        let mut path = path::PathBuf::from(manifest_dir);
-        path.push("resources");
-        path
-    } else {
-        path::PathBuf::from("./resources")
-    };
+    let resource_dir = get_resource_dir();
 
     println!("{:?}", resource_dir);
     
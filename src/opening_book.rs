@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::board::*;
+use crate::brain::*;
+use crate::grid::*;
+use crate::symmetry::*;
+use crate::cli::*;
+
+pub const OPENING_BOOK_PATH: &str = "/opening_book.json";
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct BookMove {
+    pub play: String,
+    pub weight: u32,
+}
+
+// Candidate moves indexed by the notation of the position they are played from.
+#[derive(Clone, Default)]
+#[derive(Debug)]
+#[derive(Serialize, Deserialize)]
+pub struct OpeningBook {
+    pub positions: BTreeMap<String, Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    pub fn load<R: Read>(reader: R) -> Result<OpeningBook, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    pub fn load_from_file() -> Result<OpeningBook, String> {
+        let mut path = get_resource_dir();
+        path.push(OPENING_BOOK_PATH.trim_start_matches('/'));
+        let file = std::fs::File::open(&path).map_err(|error| format!("{0:?} : {1}", path, error))?;
+        OpeningBook::load(file).map_err(|error| format!("{0:?} : {1}", path, error))
+    }
+
+    pub fn load_from_resources(ctx: &mut ggez::Context) -> ggez::GameResult<OpeningBook> {
+        let file = ggez::filesystem::open(ctx, OPENING_BOOK_PATH)?;
+        match OpeningBook::load(file) {
+            Ok(book) => Ok(book),
+            Err(error) => Err(ggez::GameError::ResourceLoadError(format!("{0} : {1}", OPENING_BOOK_PATH, error))),
+        }
+    }
+
+    pub fn add_move(&mut self, board: &BoardState, play: (usize, usize), weight: u32) {
+        self.positions.entry(board.to_notation()).or_default().push(BookMove {
+            play: Grid::get_play_name(play),
+            weight,
+        });
+    }
+
+    // Entries that do not parse or are not legal in the position are ignored.
+    // A position missing from the book uses the entries of a symmetric one.
+    pub fn get_moves(&self, board: &BoardState) -> Vec<((usize, usize), u32)> {
+        let found = BoardTransform::all().into_iter()
+            .find_map(|transform| self.positions.get(&board.transform(transform).to_notation()).map(|entries| (entries, transform)));
+        let (entries, transform) = match found {
+            Some(found) => found,
+            None => return Vec::new(),
+        };
+
+        let legal_plays = Brain::find_all_plays(board, board.current_player);
+        entries.iter()
+            .filter(|entry| entry.weight > 0)
+            .filter_map(|entry| Grid::get_play_from_name(&entry.play).map(|play| (transform.inverse().apply_to_play(play), entry.weight)))
+            .filter(|(play, _)| legal_plays.contains(play))
+            .collect()
+    }
+
+    pub fn pick_move<R: Rng>(&self, board: &BoardState, rng: &mut R) -> Option<(usize, usize)> {
+        let moves = self.get_moves(board);
+        let total: u32 = moves.iter().map(|(_, weight)| weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0..total);
+        for (play, weight) in moves {
+            if pick < weight {
+                return Some(play);
+            }
+
+            pick -= weight;
+        }
+
+        None
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::{
+    Arc,
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, Receiver, TryRecvError},
+};
+use std::thread;
+
+use crate::board::*;
+use crate::brain::*;
+use crate::agent::*;
+
+pub enum SearchPoll {
+    Pending,
+    Done(Option<(usize, usize)>),
+}
+
+// Lets an agent choose its move on a worker thread, the result is collected by polling the handle.
+// Dropping the handle cancels the search.
+pub struct SearchHandle {
+    receiver: Receiver<Option<(usize, usize)>>,
+    cancel: Arc<AtomicBool>,
+    progress: Arc<Mutex<Option<SearchInfo>>>,
+}
+
+impl SearchHandle {
+    pub fn start(board: BoardState, agent: SharedAgent) -> SearchHandle {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let progress = Arc::new(Mutex::new(None));
+        let thread_progress = progress.clone();
+
+        thread::spawn(move || {
+            let limits = AgentLimits {
+                move_time: None,
+                cancel: Some(&thread_cancel),
+                progress: Some(&thread_progress),
+            };
+
+            // A previous search of the same agent may still be finishing after being cancelled.
+            let play = match agent.lock() {
+                Ok(mut agent) => agent.choose_move(&board, &limits),
+                Err(_) => None,
+            };
+
+            if !thread_cancel.load(Ordering::Relaxed) {
+                // The handle may already be gone, in which case nobody is waiting for this result.
+                let _ = sender.send(play);
+            }
+        });
+
+        SearchHandle {
+            receiver,
+            cancel,
+            progress,
+        }
+    }
+
+    // The latest continuation reported by the search, if any.
+    pub fn get_progress(&self) -> Option<SearchInfo> {
+        self.progress.lock().unwrap().clone()
+    }
+
+    pub fn poll(&self) -> SearchPoll {
+        match self.receiver.try_recv() {
+            Ok(play) => SearchPoll::Done(play),
+            Err(TryRecvError::Empty) => SearchPoll::Pending,
+            Err(TryRecvError::Disconnected) => SearchPoll::Done(None),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::game::*;
+use crate::board::*;
+use crate::brain::*;
+use crate::agent::*;
+
+pub const MAX_GAME_PLIES: usize = 200;
+pub const MAX_OPENING_ATTEMPTS: u32 = 100;
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub enum GameOutcome {
+    Winner(PlayerSide),
+    Draw,
+}
+
+pub struct GameSummary {
+    pub outcome: GameOutcome,
+    pub plies: usize,
+    pub top_time: Duration,
+    pub top_moves: u32,
+    pub bottom_time: Duration,
+    pub bottom_moves: u32,
+}
+
+pub struct MatchScore {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MatchScore {
+    pub fn new() -> MatchScore {
+        MatchScore { wins: 0, draws: 0, losses: 0 }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    pub fn points(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 * 0.5
+    }
+
+    pub fn record(&mut self, outcome: GameOutcome, side: PlayerSide) {
+        match outcome {
+            GameOutcome::Winner(winner) if winner == side => self.wins += 1,
+            GameOutcome::Winner(_) => self.losses += 1,
+            GameOutcome::Draw => self.draws += 1,
+        }
+    }
+
+    // Elo difference with the bounds of its 95% confidence interval, computed from the variance of the game scores.
+    pub fn get_elo(&self) -> (f64, f64, f64) {
+        let games = self.games() as f64;
+        if games == 0. {
+            return (0., f64::NEG_INFINITY, f64::INFINITY);
+        }
+
+        let score = self.points() / games;
+        let variance = (self.wins as f64 * (1. - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2)) / games;
+        let margin = 1.96 * (variance / games).sqrt();
+
+        (score_to_elo(score), score_to_elo(score - margin), score_to_elo(score + margin))
+    }
+}
+
+impl Default for MatchScore {
+    fn default() -> Self {
+        MatchScore::new()
+    }
+}
+
+pub fn score_to_elo(score: f64) -> f64 {
+    if score <= 0. {
+        return f64::NEG_INFINITY;
+    }
+
+    if score >= 1. {
+        return f64::INFINITY;
+    }
+
+    -400. * (1. / score - 1.).log10()
+}
+
+// Random plays from board, tried again until they do not end the game. After MAX_OPENING_ATTEMPTS lines all ending it,
+// the last one stops before its last ply. A finished board is returned as it is.
+pub fn play_random_opening<R: Rng>(board: &BoardState, plies: u32, rng: &mut R) -> BoardState {
+    let mut opening = *board;
+    if board.get_winner().is_some() {
+        return opening;
+    }
+
+    for _ in 0..MAX_OPENING_ATTEMPTS {
+        opening = *board;
+        let mut is_finished = false;
+        for _ in 0..plies {
+            let plays = Brain::find_all_plays(&opening, opening.current_player);
+            let next_board = match plays.choose(rng) {
+                Some(play) => opening.make_move(play.0, play.1),
+                None => break,
+            };
+
+            is_finished = next_board.get_winner().is_some();
+            if is_finished {
+                break;
+            }
+
+            opening = next_board;
+        }
+
+        if !is_finished {
+            return opening;
+        }
+    }
+
+    opening
+}
+
+pub fn play_game(board: &BoardState, top: &mut dyn Agent, bottom: &mut dyn Agent, max_plies: usize) -> GameSummary {
+    let mut board = *board;
+    let mut summary = GameSummary {
+        outcome: GameOutcome::Draw,
+        plies: 0,
+        top_time: Duration::ZERO,
+        top_moves: 0,
+        bottom_time: Duration::ZERO,
+        bottom_moves: 0,
+    };
+
+    while summary.plies < max_plies {
+        if let Some(winner) = board.get_winner() {
+            summary.outcome = GameOutcome::Winner(winner);
+            return summary;
+        }
+
+        let start = Instant::now();
+        let play = match board.current_player {
+            PlayerSide::Top => top.choose_move(&board, &AgentLimits::default()),
+            PlayerSide::Bottom => bottom.choose_move(&board, &AgentLimits::default()),
+        };
+        let elapsed = start.elapsed();
+
+        match board.current_player {
+            PlayerSide::Top => {
+                summary.top_time += elapsed;
+                summary.top_moves += 1;
+            },
+            PlayerSide::Bottom => {
+                summary.bottom_time += elapsed;
+                summary.bottom_moves += 1;
+            },
+        }
+
+        match play {
+            Some(play) => board = board.make_move(play.0, play.1),
+            None => {
+                summary.outcome = GameOutcome::Winner(board.current_player.reverse());
+                return summary;
+            },
+        }
+
+        summary.plies += 1;
+    }
+
+    if let Some(winner) = board.get_winner() {
+        summary.outcome = GameOutcome::Winner(winner);
+    }
+
+    summary
+}
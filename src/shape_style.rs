@@ -4,4 +4,6 @@ pub enum ShapeStyle {
     Hovered,
     Highlight,
     Press,
+    Hint,
+    Threat,
 }
\ No newline at end of file
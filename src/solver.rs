@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+use crate::brain::*;
+
+pub const DEFAULT_SOLVER_DEPTH: u32 = 40;
+pub const DEFAULT_MAX_ENTRIES: usize = 1 << 22;
+// Nodes between two progress reports within a depth.
+const PROGRESS_INTERVAL: u64 = 1 << 20;
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub enum SolverResult {
+    // Plies until the side to move has eliminated the other one, or has been eliminated, with the best play of both sides.
+    Win(u32),
+    Loss(u32),
+    // Neither side can force the end of the game within the depth searched.
+    Unknown,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+pub struct SolverProgress {
+    pub depth: u32,
+    pub nodes: u64,
+    pub entries: usize,
+    pub elapsed: Duration,
+    // Whether the depth has been searched completely.
+    pub completed: bool,
+}
+
+// The board of a smaller grid, made of the tiles of the full grid with the same coordinates.
+// The pawns may not leave it, the game is otherwise the same.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+pub struct ReducedBoard {
+    pub size: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy)]
+struct TranspositionEntry {
+    depth: u32,
+    score: i32,
+    bound: Bound,
+}
+
+// Exhaustive alpha-beta with a transposition table, the depth growing until the result of the position is proven.
+pub struct Solver {
+    pub board: ReducedBoard,
+    // The table is cleared once it holds that many positions.
+    pub max_entries: usize,
+    pub nodes: u64,
+    table: HashMap<u128, TranspositionEntry>,
+}
+
+impl SolverResult {
+    fn from_score(score: i32) -> SolverResult {
+        match score {
+            score if score > MATE_SCORE / 2 => SolverResult::Win((MATE_SCORE - score) as u32),
+            score if score < -MATE_SCORE / 2 => SolverResult::Loss((MATE_SCORE + score) as u32),
+            _ => SolverResult::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for SolverResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverResult::Win(plies) => write!(f, "win in {} plies", plies),
+            SolverResult::Loss(plies) => write!(f, "loss in {} plies", plies),
+            SolverResult::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl ReducedBoard {
+    pub fn new(size: usize) -> Result<ReducedBoard, String> {
+        match size {
+            2..=GRID_SIDE => Ok(ReducedBoard { size }),
+            _ => Err(format!("The size of the board must be between 2 and {}", GRID_SIDE)),
+        }
+    }
+
+    // As on the full grid, the last row only has quad tiles.
+    pub fn contains(&self, tile_index: usize) -> bool {
+        let coord = Grid::get_coord_from_index(tile_index);
+        let size = self.size as i32;
+        coord.x <= size * 2 && (coord.y < size || (coord.y == size && coord.x % 2 == 0))
+    }
+
+    pub fn get_tile_count(&self) -> usize {
+        (0..NUMBER_OF_TILES).filter(|tile_index| self.contains(*tile_index)).count()
+    }
+
+    pub fn contains_position(&self, board: &BoardState) -> bool {
+        (0..NUMBER_OF_TILES).all(|tile_index| board.tiles[tile_index].is_none() || self.contains(tile_index))
+    }
+
+    // The starting position of the full grid, scaled down around the middle column.
+    pub fn starting_position(&self) -> BoardState {
+        let size = self.size as i32;
+        let middle = size;
+        let bottom_coord = match middle % 2 {
+            0 => TileCoord { x: middle, y: size },
+            _ => TileCoord { x: middle, y: size - 1 },
+        };
+
+        let top_coords = [
+            TileCoord { x: middle - 1, y: 0 },
+            TileCoord { x: middle, y: 0 },
+            TileCoord { x: middle + 1, y: 0 },
+            TileCoord { x: middle, y: 1 },
+        ];
+        let bottom_coords = [
+            TileCoord { x: middle - 1, y: size - 1 },
+            bottom_coord,
+            TileCoord { x: middle + 1, y: size - 1 },
+        ];
+
+        let mut board = BoardState::empty();
+        for (coords, player) in [(&top_coords[..], PlayerSide::Top), (&bottom_coords[..], PlayerSide::Bottom)] {
+            for coord in coords {
+                match Grid::get_index_from_coord(*coord) {
+                    Some(tile_index) if self.contains(tile_index) && board.tiles[tile_index].is_none() => board.add_pawn(*coord, player),
+                    _ => {},
+                }
+            }
+        }
+
+        board
+    }
+
+    // In the order of the alpha-beta search of the AI.
+    pub fn get_plays(&self, board: &BoardState) -> Vec<(usize, usize)> {
+        let plays = Brain::find_all_plays(board, board.current_player).into_iter()
+            .filter(|play| self.contains(play.1))
+            .collect();
+        Brain::order_plays(board, plays)
+    }
+}
+
+impl Solver {
+    pub fn new(board: ReducedBoard) -> Solver {
+        Solver {
+            board,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            nodes: 0,
+            table: HashMap::new(),
+        }
+    }
+
+    // Returns the result and the expected continuation, on_progress being called regularly and after each depth.
+    pub fn solve<F: FnMut(&SolverProgress)>(&mut self, position: &BoardState, max_depth: u32, mut on_progress: F) -> Result<(SolverResult, Vec<(usize, usize)>), String> {
+        if !self.board.contains_position(position) {
+            return Err(format!("Some pawns are out of the board of size {}", self.board.size));
+        }
+
+        let start = Instant::now();
+        let mut result = SolverResult::Unknown;
+        for depth in 1..=max_depth {
+            let mut report = |nodes: u64, entries: usize, completed: bool| on_progress(&SolverProgress {
+                depth,
+                nodes,
+                entries,
+                elapsed: start.elapsed(),
+                completed,
+            });
+
+            let score = self.negamax(position, depth, 0, -MATE_SCORE, MATE_SCORE, &mut report);
+            report(self.nodes, self.table.len(), true);
+            result = SolverResult::from_score(score);
+            if result != SolverResult::Unknown {
+                break;
+            }
+        }
+
+        let line = match result {
+            SolverResult::Win(plies) | SolverResult::Loss(plies) => self.get_proof_line(position, plies),
+            SolverResult::Unknown => Vec::new(),
+        };
+
+        Ok((result, line))
+    }
+
+    // The side to move being eliminated at a given ply scores -(MATE_SCORE - ply), an earlier win scoring higher.
+    // u64::is_multiple_of needs Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    fn negamax(&mut self, board: &BoardState, depth: u32, ply: u32, mut alpha: i32, mut beta: i32, report: &mut dyn FnMut(u64, usize, bool)) -> i32 {
+        self.nodes += 1;
+        if self.nodes % PROGRESS_INTERVAL == 0 {
+            report(self.nodes, self.table.len(), false);
+        }
+
+        let plays = match board.get_winner() {
+            Some(_) => Vec::new(),
+            None => self.board.get_plays(board),
+        };
+
+        if plays.is_empty() {
+            return -(MATE_SCORE - ply as i32);
+        }
+
+        if depth == 0 {
+            return 0;
+        }
+
+        let key = board.get_key();
+        if let Some(entry) = self.table.get(&key) {
+            if entry.depth >= depth {
+                // Results are stored relative to the position, not to the root.
+                let score = match entry.score {
+                    score if score > MATE_SCORE / 2 => score - ply as i32,
+                    score if score < -MATE_SCORE / 2 => score + ply as i32,
+                    score => score,
+                };
+
+                match entry.bound {
+                    Bound::Exact => return score,
+                    Bound::Lower => alpha = alpha.max(score),
+                    Bound::Upper => beta = beta.min(score),
+                }
+
+                if alpha >= beta {
+                    return score;
+                }
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut best_score = -MATE_SCORE;
+        for play in plays {
+            let next_board = board.make_move(play.0, play.1);
+            let score = -self.negamax(&next_board, depth - 1, ply + 1, -beta, -alpha, report);
+            best_score = best_score.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = match best_score {
+            score if score <= original_alpha => Bound::Upper,
+            score if score >= beta => Bound::Lower,
+            _ => Bound::Exact,
+        };
+
+        if self.table.len() >= self.max_entries {
+            self.table.clear();
+        }
+
+        self.table.insert(key, TranspositionEntry {
+            depth,
+            score: match best_score {
+                score if score > MATE_SCORE / 2 => score + ply as i32,
+                score if score < -MATE_SCORE / 2 => score - ply as i32,
+                score => score,
+            },
+            bound,
+        });
+
+        best_score
+    }
+
+    // Follows the best play of both sides until the end of the game, proven to come after that many plies,
+    // the winning side taking the shortest way and the losing one the longest.
+    fn get_proof_line(&mut self, position: &BoardState, plies: u32) -> Vec<(usize, usize)> {
+        let mut line = Vec::new();
+        let mut board = *position;
+        let mut ignore_progress = |_: u64, _: usize, _: bool| {};
+        while board.get_winner().is_none() && (line.len() as u32) < plies {
+            let depth = plies - line.len() as u32;
+            let mut best: Option<((usize, usize), i32)> = None;
+            for play in self.board.get_plays(&board) {
+                let next_board = board.make_move(play.0, play.1);
+                let score = -self.negamax(&next_board, depth - 1, 1, -MATE_SCORE, MATE_SCORE, &mut ignore_progress);
+                match best {
+                    Some((_, best_score)) if best_score >= score => {},
+                    _ => best = Some((play, score)),
+                }
+            }
+
+            match best {
+                Some((play, _)) => {
+                    line.push(play);
+                    board = board.make_move(play.0, play.1);
+                },
+                None => break,
+            }
+        }
+
+        line
+    }
+}
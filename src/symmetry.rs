@@ -0,0 +1,99 @@
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+
+// The symmetries of the game : the grid is left-right symmetric, and upside down with the sides swapped
+// a position plays the same as the original one.
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub struct BoardTransform {
+    pub mirror: bool,
+    pub swap_sides: bool,
+}
+
+impl BoardTransform {
+    pub const IDENTITY: BoardTransform = BoardTransform { mirror: false, swap_sides: false };
+
+    pub fn all() -> [BoardTransform; 4] {
+        [
+            BoardTransform::IDENTITY,
+            BoardTransform { mirror: true, swap_sides: false },
+            BoardTransform { mirror: false, swap_sides: true },
+            BoardTransform { mirror: true, swap_sides: true },
+        ]
+    }
+
+    pub fn apply_to_tile(self, tile_index: usize) -> usize {
+        let tile_index = match self.mirror {
+            true => Grid::get_mirrored_index(tile_index),
+            false => tile_index,
+        };
+
+        match self.swap_sides {
+            true => Grid::get_flipped_index(tile_index),
+            false => tile_index,
+        }
+    }
+
+    pub fn apply_to_play(self, play: (usize, usize)) -> (usize, usize) {
+        (self.apply_to_tile(play.0), self.apply_to_tile(play.1))
+    }
+
+    // Every transform undoes itself, a play of the transformed position is brought back with the same transform.
+    pub fn inverse(self) -> BoardTransform {
+        self
+    }
+}
+
+impl BoardState {
+    pub fn transform(&self, transform: BoardTransform) -> BoardState {
+        let mut board = BoardState::empty();
+        board.current_player = match transform.swap_sides {
+            true => self.current_player.reverse(),
+            false => self.current_player,
+        };
+
+        for tile_index in 0..NUMBER_OF_TILES {
+            if let Some(pawn) = self.tiles[tile_index] {
+                let player = match transform.swap_sides {
+                    true => pawn.player.reverse(),
+                    false => pawn.player,
+                };
+
+                board.add_pawn(Grid::get_coord_from_index(transform.apply_to_tile(tile_index)), player);
+            }
+        }
+
+        board
+    }
+
+    pub fn mirror(&self) -> BoardState {
+        self.transform(BoardTransform { mirror: true, swap_sides: false })
+    }
+
+    pub fn swap_sides(&self) -> BoardState {
+        self.transform(BoardTransform { mirror: false, swap_sides: true })
+    }
+
+    // The same position for all the symmetric ones, with the bottom side to move. Returns the transform
+    // giving it, which also brings the plays of the canonical position back to this one.
+    pub fn canonical(&self) -> (BoardState, BoardTransform) {
+        let mut best: Option<(BoardState, BoardTransform, u128)> = None;
+        for transform in BoardTransform::all() {
+            let board = self.transform(transform);
+            if board.current_player != PlayerSide::Bottom {
+                continue;
+            }
+
+            let key = board.get_key();
+            match best {
+                Some((_, _, best_key)) if best_key <= key => {},
+                _ => best = Some((board, transform, key)),
+            }
+        }
+
+        let (board, transform, _) = best.unwrap();
+        (board, transform)
+    }
+}
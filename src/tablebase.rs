@@ -0,0 +1,435 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+use crate::brain::*;
+use crate::cli::*;
+
+pub const TABLEBASE_DIR: &str = "/tablebases";
+pub const DEFAULT_TABLEBASE_PAWNS: usize = 2;
+const TABLEBASE_MAGIC: &[u8; 4] = b"OCT2";
+// Set in the stored value of the positions won by the side to move.
+const WIN_FLAG: u8 = 0x80;
+// The plies of the longest game stored, plus one, have to fit below WIN_FLAG.
+pub const MAX_TABLEBASE_PLIES: u32 = WIN_FLAG as u32 - 2;
+
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+#[derive(Debug)]
+pub enum TablebaseResult {
+    // Plies until the side to move has eliminated the other one, or has been eliminated, with the best play of both sides.
+    Win(u32),
+    Loss(u32),
+    Draw,
+}
+
+// Every position with a given number of pawns on each side, one byte per position :
+// 0 for a draw, otherwise the number of plies until the end of the game plus one, with WIN_FLAG set for a win of the side to move.
+pub struct Table {
+    pub top_count: usize,
+    pub bottom_count: usize,
+    values: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct Tablebase {
+    tables: BTreeMap<(usize, usize), Table>,
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    let mut result = 1;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+
+    result
+}
+
+// Rank of a sorted combination among all the combinations of as many elements, in colexicographic order.
+fn rank_combination(elements: &[usize]) -> usize {
+    elements.iter().enumerate().map(|(i, element)| binomial(*element, i + 1)).sum()
+}
+
+fn unrank_combination(mut rank: usize, count: usize) -> Vec<usize> {
+    let mut elements = vec![0; count];
+    for i in (0..count).rev() {
+        let mut element = i;
+        while binomial(element + 1, i + 1) <= rank {
+            element += 1;
+        }
+
+        rank -= binomial(element, i + 1);
+        elements[i] = element;
+    }
+
+    elements
+}
+
+impl TablebaseResult {
+    fn decode(value: u8) -> TablebaseResult {
+        match value {
+            0 => TablebaseResult::Draw,
+            value if value & WIN_FLAG != 0 => TablebaseResult::Win((value & !WIN_FLAG) as u32 - 1),
+            value => TablebaseResult::Loss(value as u32 - 1),
+        }
+    }
+
+    fn encode(self) -> u8 {
+        match self {
+            TablebaseResult::Win(plies) => WIN_FLAG | (plies as u8 + 1),
+            TablebaseResult::Loss(plies) => plies as u8 + 1,
+            TablebaseResult::Draw => 0,
+        }
+    }
+
+    // The result for the side which played the move leading to this position.
+    pub fn previous_ply(self) -> TablebaseResult {
+        match self {
+            TablebaseResult::Win(plies) => TablebaseResult::Loss(plies + 1),
+            TablebaseResult::Loss(plies) => TablebaseResult::Win(plies + 1),
+            TablebaseResult::Draw => TablebaseResult::Draw,
+        }
+    }
+
+    // Higher is better for the side to move. On the mate scale of the search, where the side to move being eliminated
+    // scores -(MATE_SCORE - ply), ply being the number of plies between the root of the search and this position.
+    pub fn get_score(self, ply: u32) -> i32 {
+        match self {
+            TablebaseResult::Win(plies) => MATE_SCORE - (ply + plies) as i32,
+            TablebaseResult::Loss(plies) => -(MATE_SCORE - (ply + plies) as i32),
+            TablebaseResult::Draw => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for TablebaseResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TablebaseResult::Win(plies) => write!(f, "win in {} plies", plies),
+            TablebaseResult::Loss(plies) => write!(f, "loss in {} plies", plies),
+            TablebaseResult::Draw => write!(f, "draw"),
+        }
+    }
+}
+
+impl Table {
+    pub fn get_size(top_count: usize, bottom_count: usize) -> usize {
+        binomial(NUMBER_OF_TILES, top_count) * binomial(NUMBER_OF_TILES - top_count, bottom_count) * 2
+    }
+
+    pub fn get_file_name(top_count: usize, bottom_count: usize) -> String {
+        format!("t{0}b{1}.tb", top_count, bottom_count)
+    }
+
+    // The top pawns are ranked among all the tiles, then the bottom pawns among the tiles left free.
+    fn get_index(board: &BoardState) -> usize {
+        let mut top_tiles = board.top_pawns.tile_indexes[..board.top_pawns.count].to_vec();
+        top_tiles.sort_unstable();
+        let mut bottom_tiles: Vec<usize> = board.bottom_pawns.tile_indexes[..board.bottom_pawns.count].iter()
+            .map(|tile| tile - top_tiles.iter().filter(|top_tile| *top_tile < tile).count())
+            .collect();
+        bottom_tiles.sort_unstable();
+
+        let bottom_combinations = binomial(NUMBER_OF_TILES - top_tiles.len(), bottom_tiles.len());
+        let side = match board.current_player { PlayerSide::Top => 0, PlayerSide::Bottom => 1 };
+        (rank_combination(&top_tiles) * bottom_combinations + rank_combination(&bottom_tiles)) * 2 + side
+    }
+
+    pub fn get_position(top_count: usize, bottom_count: usize, index: usize) -> BoardState {
+        let bottom_combinations = binomial(NUMBER_OF_TILES - top_count, bottom_count);
+        let top_tiles = unrank_combination(index / 2 / bottom_combinations, top_count);
+        let free_tiles: Vec<usize> = (0..NUMBER_OF_TILES).filter(|tile| !top_tiles.contains(tile)).collect();
+
+        let mut board = BoardState::empty();
+        board.current_player = match index % 2 { 0 => PlayerSide::Top, _ => PlayerSide::Bottom };
+        for tile in &top_tiles {
+            board.add_pawn(Grid::get_coord_from_index(*tile), PlayerSide::Top);
+        }
+
+        for free_tile in unrank_combination(index / 2 % bottom_combinations, bottom_count) {
+            board.add_pawn(Grid::get_coord_from_index(free_tiles[free_tile]), PlayerSide::Bottom);
+        }
+
+        board
+    }
+
+    pub fn get(&self, index: usize) -> TablebaseResult {
+        TablebaseResult::decode(self.values[index])
+    }
+
+    // Counts of wins, losses and draws for the side to move, and the longest win.
+    pub fn get_summary(&self) -> (usize, usize, usize, u32) {
+        let mut summary = (0, 0, 0, 0);
+        for index in 0..self.values.len() {
+            match self.get(index) {
+                TablebaseResult::Win(plies) => {
+                    summary.0 += 1;
+                    summary.3 = summary.3.max(plies);
+                },
+                TablebaseResult::Loss(_) => summary.1 += 1,
+                TablebaseResult::Draw => summary.2 += 1,
+            }
+        }
+
+        summary
+    }
+
+    fn get_longest_game(&self) -> u32 {
+        (0..self.values.len())
+            .map(|index| match self.get(index) {
+                TablebaseResult::Win(plies) | TablebaseResult::Loss(plies) => plies,
+                TablebaseResult::Draw => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Retrograde analysis : the positions lost right away are found first, then every pass finds the positions
+    // won by reaching a position lost in one ply less, and the positions lost whatever the play, the longest defence
+    // taking one ply less. Positions never reached this way are draws.
+    // The tables reached by a capture have to be in the tablebase already.
+    pub fn generate<F: FnMut(u32, usize)>(top_count: usize, bottom_count: usize, tablebase: &Tablebase, mut on_pass: F) -> Result<Table, String> {
+        if top_count == 0 || bottom_count == 0 || top_count > MAX_PAWN_NUMBER || bottom_count > MAX_PAWN_NUMBER {
+            return Err(format!("Invalid material {0} against {1}", top_count, bottom_count));
+        }
+
+        let mut longest_capture_game = 0;
+        for key in [(top_count - 1, bottom_count), (top_count, bottom_count - 1)] {
+            if key.0 == 0 || key.1 == 0 {
+                continue;
+            }
+
+            match tablebase.tables.get(&key) {
+                Some(table) => longest_capture_game = longest_capture_game.max(table.get_longest_game()),
+                None => return Err(format!("The {0} against {1} table is needed first", key.0, key.1)),
+            }
+        }
+
+        let size = Table::get_size(top_count, bottom_count);
+        let mut values = vec![0_u8; size];
+        let mut solved = vec![false; size];
+        let mut plies = 0;
+        loop {
+            let mut solved_count = 0;
+            for index in 0..size {
+                if solved[index] {
+                    continue;
+                }
+
+                let board = Table::get_position(top_count, bottom_count, index);
+                let mut is_win = false;
+                let mut is_loss = true;
+                let mut longest_defence = 0;
+                for play in Brain::find_all_plays(&board, board.current_player) {
+                    let next_board = board.make_move(play.0, play.1);
+                    let result = match (next_board.top_pawns.count, next_board.bottom_pawns.count) {
+                        (top, bottom) if top == top_count && bottom == bottom_count => {
+                            let next_index = Table::get_index(&next_board);
+                            match solved[next_index] {
+                                true => Some(TablebaseResult::decode(values[next_index])),
+                                false => None,
+                            }
+                        },
+                        _ => tablebase.probe(&next_board),
+                    };
+
+                    match result {
+                        Some(TablebaseResult::Loss(next_plies)) if next_plies < plies => {
+                            is_win = true;
+                            break;
+                        },
+                        Some(TablebaseResult::Win(next_plies)) => longest_defence = longest_defence.max(next_plies + 1),
+                        _ => is_loss = false,
+                    }
+                }
+
+                let result = match (is_win, is_loss && longest_defence == plies) {
+                    (true, _) => TablebaseResult::Win(plies),
+                    (false, true) => TablebaseResult::Loss(plies),
+                    (false, false) => continue,
+                };
+
+                if plies > MAX_TABLEBASE_PLIES {
+                    return Err(format!("Games longer than {} plies cannot be stored", MAX_TABLEBASE_PLIES));
+                }
+
+                values[index] = result.encode();
+                solved[index] = true;
+                solved_count += 1;
+            }
+
+            on_pass(plies, solved_count);
+            if solved_count == 0 && plies > longest_capture_game + 1 {
+                break;
+            }
+
+            plies += 1;
+        }
+
+        Ok(Table {
+            top_count,
+            bottom_count,
+            values,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = TABLEBASE_MAGIC.to_vec();
+        bytes.push(self.top_count as u8);
+        bytes.push(self.bottom_count as u8);
+        bytes.extend_from_slice(&self.values);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Table, String> {
+        if bytes.len() < 6 || &bytes[0..4] != TABLEBASE_MAGIC {
+            return Err(String::from("Not a tablebase file"));
+        }
+
+        let top_count = bytes[4] as usize;
+        let bottom_count = bytes[5] as usize;
+        if top_count == 0 || bottom_count == 0 || top_count > MAX_PAWN_NUMBER || bottom_count > MAX_PAWN_NUMBER {
+            return Err(format!("Invalid material {0} against {1}", top_count, bottom_count));
+        }
+
+        if bytes.len() - 6 != Table::get_size(top_count, bottom_count) {
+            return Err(format!("Truncated {0} against {1} table", top_count, bottom_count));
+        }
+
+        Ok(Table {
+            top_count,
+            bottom_count,
+            values: bytes[6..].to_vec(),
+        })
+    }
+}
+
+impl std::fmt::Debug for Tablebase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.tables.keys()).finish()
+    }
+}
+
+impl Tablebase {
+    pub fn add_table(&mut self, table: Table) {
+        self.tables.insert((table.top_count, table.bottom_count), table);
+    }
+
+    pub fn get_tables(&self) -> impl Iterator<Item = &Table> {
+        self.tables.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    // Loads every table found in the directory, missing ones are skipped.
+    pub fn load_from_dir(dir: &Path) -> Result<Tablebase, String> {
+        let mut tablebase = Tablebase::default();
+        for top_count in 1..=MAX_PAWN_NUMBER {
+            for bottom_count in 1..=MAX_PAWN_NUMBER {
+                let path = dir.join(Table::get_file_name(top_count, bottom_count));
+                if !path.exists() {
+                    continue;
+                }
+
+                let bytes = std::fs::read(&path).map_err(|error| format!("{0:?} : {1}", path, error))?;
+                tablebase.add_table(Table::from_bytes(&bytes).map_err(|error| format!("{0:?} : {1}", path, error))?);
+            }
+        }
+
+        match tablebase.is_empty() {
+            true => Err(format!("{:?} : no tablebase file", dir)),
+            false => Ok(tablebase),
+        }
+    }
+
+    pub fn load_from_file() -> Result<Tablebase, String> {
+        let mut path = get_resource_dir();
+        path.push(TABLEBASE_DIR.trim_start_matches('/'));
+        Tablebase::load_from_dir(&path)
+    }
+
+    pub fn load_from_resources(ctx: &mut ggez::Context) -> ggez::GameResult<Tablebase> {
+        let mut tablebase = Tablebase::default();
+        for top_count in 1..=MAX_PAWN_NUMBER {
+            for bottom_count in 1..=MAX_PAWN_NUMBER {
+                let path = format!("{0}/{1}", TABLEBASE_DIR, Table::get_file_name(top_count, bottom_count));
+                if !ggez::filesystem::exists(ctx, &path) {
+                    continue;
+                }
+
+                let mut bytes = Vec::new();
+                ggez::filesystem::open(ctx, &path)?.read_to_end(&mut bytes)?;
+                match Table::from_bytes(&bytes) {
+                    Ok(table) => tablebase.add_table(table),
+                    Err(error) => return Err(ggez::GameError::ResourceLoadError(format!("{0} : {1}", path, error))),
+                }
+            }
+        }
+
+        match tablebase.is_empty() {
+            true => Err(ggez::GameError::ResourceLoadError(format!("{} : no tablebase file", TABLEBASE_DIR))),
+            false => Ok(tablebase),
+        }
+    }
+
+    pub fn save_to_dir(&self, dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(dir).map_err(|error| format!("{0:?} : {1}", dir, error))?;
+        for table in self.tables.values() {
+            let path = dir.join(Table::get_file_name(table.top_count, table.bottom_count));
+            std::fs::write(&path, table.to_bytes()).map_err(|error| format!("{0:?} : {1}", path, error))?;
+        }
+
+        Ok(())
+    }
+
+    // None when the material of the position has no table.
+    pub fn probe(&self, board: &BoardState) -> Option<TablebaseResult> {
+        if board.get_winner().is_some() {
+            return Some(TablebaseResult::Loss(0));
+        }
+
+        let table = self.tables.get(&(board.top_pawns.count, board.bottom_pawns.count))?;
+        Some(table.get(Table::get_index(board)))
+    }
+
+    // The fastest win, the longest defence or a play keeping the draw.
+    pub fn get_best_play(&self, board: &BoardState) -> Option<((usize, usize), TablebaseResult)> {
+        self.probe(board)?;
+        let mut best: Option<((usize, usize), TablebaseResult)> = None;
+        for play in Brain::find_all_plays(board, board.current_player) {
+            let result = self.probe(&board.make_move(play.0, play.1))?.previous_ply();
+            match best {
+                Some((_, best_result)) if best_result.get_score(0) >= result.get_score(0) => {},
+                _ => best = Some((play, result)),
+            }
+        }
+
+        best
+    }
+
+    // The continuation both sides would play, drawn games being cut after max_plies.
+    pub fn get_line(&self, board: &BoardState, max_plies: usize) -> Vec<(usize, usize)> {
+        let mut line = Vec::new();
+        let mut board = *board;
+        while line.len() < max_plies && board.get_winner().is_none() {
+            match self.get_best_play(&board) {
+                Some((play, _)) => {
+                    line.push(play);
+                    board = board.make_move(play.0, play.1);
+                },
+                None => break,
+            }
+        }
+
+        line
+    }
+}
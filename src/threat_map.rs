@@ -0,0 +1,66 @@
+use crate::game::*;
+use crate::grid::*;
+use crate::board::*;
+
+// Which pawns can reach each tile in one move, whatever stands on it : a pawn attacks the enemy pawns
+// it can capture and defends the friendly pawns it could recapture on.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct ThreatMap {
+    top_attackers: Vec<Vec<usize>>,
+    bottom_attackers: Vec<Vec<usize>>,
+}
+
+impl ThreatMap {
+    pub fn new(board: &BoardState) -> ThreatMap {
+        let mut threat_map = ThreatMap {
+            top_attackers: vec![Vec::new(); NUMBER_OF_TILES],
+            bottom_attackers: vec![Vec::new(); NUMBER_OF_TILES],
+        };
+
+        for (player, pawn_array) in [(PlayerSide::Top, &board.top_pawns), (PlayerSide::Bottom, &board.bottom_pawns)] {
+            for pawn_tile in &pawn_array.tile_indexes[..pawn_array.count] {
+                for tile_index in board.get_possible_moves(*pawn_tile) {
+                    match player {
+                        PlayerSide::Top => threat_map.top_attackers[tile_index].push(*pawn_tile),
+                        PlayerSide::Bottom => threat_map.bottom_attackers[tile_index].push(*pawn_tile),
+                    }
+                }
+            }
+        }
+
+        threat_map
+    }
+
+    // Tiles of the pawns of player able to move onto the tile.
+    pub fn get_attackers(&self, tile_index: usize, player: PlayerSide) -> &[usize] {
+        match player {
+            PlayerSide::Top => &self.top_attackers[tile_index],
+            PlayerSide::Bottom => &self.bottom_attackers[tile_index],
+        }
+    }
+
+    // Whether player could answer a capture on the tile by capturing back.
+    pub fn is_defended(&self, tile_index: usize, player: PlayerSide) -> bool {
+        !self.get_attackers(tile_index, player).is_empty()
+    }
+
+    pub fn get_defended_tiles(&self, player: PlayerSide) -> Vec<usize> {
+        (0..NUMBER_OF_TILES).filter(|tile_index| self.is_defended(*tile_index, player)).collect()
+    }
+
+    // Pawns of player which the other side can capture without a recapture.
+    pub fn get_hanging_pawns(&self, board: &BoardState, player: PlayerSide) -> Vec<usize> {
+        let pawn_array = match player { PlayerSide::Top => &board.top_pawns, PlayerSide::Bottom => &board.bottom_pawns };
+        pawn_array.tile_indexes[..pawn_array.count].iter()
+            .filter(|tile_index| self.is_defended(**tile_index, player.reverse()) && !self.is_defended(**tile_index, player))
+            .copied()
+            .collect()
+    }
+}
+
+impl BoardState {
+    pub fn get_threat_map(&self) -> ThreatMap {
+        ThreatMap::new(self)
+    }
+}
@@ -87,6 +87,8 @@ impl Shape for OctoTile{
             ShapeStyle::Highlight => graphics::Color::new(0.3, 0.4, 0.5, 1_f32),
             ShapeStyle::Hovered => graphics::Color::new(0.8, 0.8, 0.8, 1_f32),
             ShapeStyle::Press => graphics::Color::new(0.9, 0.9, 0.9, 1_f32),
+            ShapeStyle::Hint => graphics::Color::new(0.3, 0.6, 0.4, 1_f32),
+            ShapeStyle::Threat => graphics::Color::new(0.7, 0.5, 0.3, 1_f32),
         };
         
         mesh_builder.polygon(graphics::DrawMode::Fill(graphics::FillOptions::default()), &self.inner_verts.to_vec(), color).unwrap();
@@ -95,6 +97,12 @@ impl Shape for OctoTile{
             ShapeStyle::Highlight => {
                 mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts.to_vec(), graphics::Color::YELLOW).unwrap();
             },
+            ShapeStyle::Hint => {
+                mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts.to_vec(), graphics::Color::GREEN).unwrap();
+            },
+            ShapeStyle::Threat => {
+                mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts, graphics::Color::new(1., 0.5, 0., 1.)).unwrap();
+            },
             _=> {},
         }
     }
@@ -115,6 +123,8 @@ impl Shape for QuadTile{
             ShapeStyle::Highlight => graphics::Color::new(0.1, 0., 0.3, 1_f32),
             ShapeStyle::Hovered => graphics::Color::new(0.8, 0.3, 0.3, 1_f32),
             ShapeStyle::Press => graphics::Color::new(0.9, 0.5, 0.5, 1_f32),
+            ShapeStyle::Hint => graphics::Color::new(0.1, 0.5, 0.2, 1_f32),
+            ShapeStyle::Threat => graphics::Color::new(0.9, 0.4, 0., 1_f32),
         };
         
         mesh_builder.polygon(graphics::DrawMode::Fill(graphics::FillOptions::default()), &self.inner_verts.to_vec(), color).unwrap();
@@ -122,6 +132,12 @@ impl Shape for QuadTile{
             ShapeStyle::Highlight => {
                 mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts.to_vec(), graphics::Color::YELLOW).unwrap();
             },
+            ShapeStyle::Hint => {
+                mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts.to_vec(), graphics::Color::GREEN).unwrap();
+            },
+            ShapeStyle::Threat => {
+                mesh_builder.polygon(graphics::DrawMode::Stroke(graphics::StrokeOptions::default().with_line_width(2.)), &self.verts, graphics::Color::new(1., 0.5, 0., 1.)).unwrap();
+            },
             _=> {},
         }
     }
@@ -19,6 +19,10 @@ impl Button {
         }
     }
 
+    pub fn set_label(self: &mut Button, label: &str) {
+        self.label = graphics::Text::new(label.to_owned());
+    }
+
     pub fn draw(self: &Button, ctx: &mut ggez::Context) -> ggez::GameResult {
         let mut mesh_builder = graphics::MeshBuilder::new();
 
@@ -0,0 +1,87 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::pawn::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+
+const GAMES: u64 = 1000;
+const MAX_PLIES: usize = 200;
+const SEED: u64 = 0;
+
+fn get_pawn_array(board: &BoardState, player: PlayerSide) -> &PawnArray {
+    match player {
+        PlayerSide::Top => &board.top_pawns,
+        PlayerSide::Bottom => &board.bottom_pawns,
+    }
+}
+
+// Every pawn of the lists is on its tile with its own index, and every pawn of the tiles is in the list of its side.
+fn assert_table_indexes(board: &BoardState) {
+    for player in [PlayerSide::Top, PlayerSide::Bottom] {
+        let pawn_array = get_pawn_array(board, player);
+        for table_index in 0..pawn_array.count {
+            match board.tiles[pawn_array.tile_indexes[table_index]] {
+                Some(pawn) => {
+                    assert!(pawn.player == player, "{}", board.to_notation());
+                    assert_eq!(pawn.table_index, table_index, "{}", board.to_notation());
+                },
+                None => panic!("{0:?} pawn {1} is on an empty tile in {2}", player, table_index, board.to_notation()),
+            }
+        }
+    }
+
+    for tile_index in 0..NUMBER_OF_TILES {
+        if let Some(pawn) = board.tiles[tile_index] {
+            let pawn_array = get_pawn_array(board, pawn.player);
+            assert!(pawn.table_index < pawn_array.count, "{}", board.to_notation());
+            assert_eq!(pawn_array.tile_indexes[pawn.table_index], tile_index, "{}", board.to_notation());
+        }
+    }
+}
+
+fn check_play(board: &BoardState, play: (usize, usize)) -> BoardState {
+    let next_board = board.make_move(play.0, play.1);
+    let context = format!("{0} played from {1}", Grid::get_play_name(play), board.to_notation());
+    if let Err(error) = next_board.check_invariants() {
+        panic!("{0} : {1}", context, error);
+    }
+
+    assert_table_indexes(&next_board);
+    assert!(next_board.current_player == board.current_player.reverse(), "the side to move did not change after {}", context);
+
+    let player = board.current_player;
+    let is_capture = board.tiles[play.1].is_some();
+    assert_eq!(get_pawn_array(&next_board, player).count, get_pawn_array(board, player).count, "{}", context);
+    assert_eq!(get_pawn_array(&next_board, player.reverse()).count + is_capture as usize, get_pawn_array(board, player.reverse()).count, "{}", context);
+
+    assert!(next_board.tiles[play.0].is_none(), "{}", context);
+    assert!(matches!(next_board.tiles[play.1], Some(pawn) if pawn.player == player), "{}", context);
+    next_board
+}
+
+#[test]
+fn random_games_keep_the_board_consistent() {
+    for game_index in 0..GAMES {
+        let mut rng = StdRng::seed_from_u64(SEED + game_index);
+        let mut board = BoardState::starting_position();
+        board.check_invariants().unwrap();
+        assert_table_indexes(&board);
+
+        for _ in 0..MAX_PLIES {
+            if board.get_winner().is_some() {
+                break;
+            }
+
+            let play = match Brain::find_all_plays(&board, board.current_player).choose(&mut rng) {
+                Some(play) => *play,
+                None => break,
+            };
+
+            board = check_play(&board, play);
+        }
+    }
+}
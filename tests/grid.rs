@@ -0,0 +1,21 @@
+use octo_chess::board::*;
+use octo_chess::grid::*;
+use octo_chess::tiles::*;
+
+// The last row only holds the quad tiles of the even columns, 37 and 39 used to be taken for octo tiles.
+#[test]
+fn last_row_tiles_are_quads() {
+    for index in NUMBER_OF_TILES - GRID_SIDE - 1..NUMBER_OF_TILES {
+        assert!(matches!(Grid::get_tile_shape_from_index(index), TileShape::Quad), "{}", index);
+    }
+}
+
+#[test]
+fn last_row_quads_only_move_to_the_octo_tiles_above() {
+    let board = BoardState::empty();
+    for (index, expected) in [(37, [28, 30]), (39, [32, 34])] {
+        let mut moves = board.get_possible_moves(index);
+        moves.sort();
+        assert_eq!(moves, expected, "{}", Grid::get_tile_name(index));
+    }
+}
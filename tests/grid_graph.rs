@@ -0,0 +1,54 @@
+use octo_chess::board::*;
+use octo_chess::grid::*;
+
+// Tiles 36 to 40 are the quad tiles of the last row, which only reach the octo tiles 28, 30, 32 and 34 above them.
+#[test]
+fn last_row_quads_are_reached_through_the_octo_tiles_above() {
+    let distances = Grid::get_distances(36, |_| false);
+    assert_eq!(distances[28], Some(1));
+    assert_eq!(distances[37], Some(2));
+    assert_eq!(distances[30], Some(2));
+    assert_eq!(distances[40], Some(5));
+    assert!(distances.iter().all(|distance| distance.is_some()));
+}
+
+#[test]
+fn blocked_tiles_are_reached_but_not_crossed() {
+    let distances = Grid::get_distances(36, |tile_index| tile_index == 30);
+    assert_eq!(distances[30], Some(2));
+    assert_eq!(distances[40], Some(7));
+}
+
+#[test]
+fn path_along_the_last_row() {
+    assert_eq!(Grid::get_path(36, 40, |_| false), Some(vec![36, 28, 30, 32, 34, 40]));
+    assert_eq!(Grid::get_path(36, 37, |_| false), Some(vec![36, 28, 37]));
+    assert_eq!(Grid::get_path(36, 40, |tile_index| tile_index == 28), None);
+}
+
+#[test]
+fn last_row_quads_are_cut_off_by_the_octo_tiles_above() {
+    let regions = Grid::get_regions(|tile_index| [28, 30, 32, 34].contains(&tile_index));
+    assert_eq!(regions.len(), 6);
+    for tile_index in 36..NUMBER_OF_TILES {
+        assert!(regions.contains(&vec![tile_index]));
+    }
+
+    assert_eq!(regions.iter().map(|region| region.len()).sum::<usize>(), NUMBER_OF_TILES - 4);
+}
+
+#[test]
+fn pawns_go_around_the_other_pawns() {
+    // Bottom pawns on tiles 36 and 29, top pawns on tiles 40 and 21.
+    let board = BoardState::from_notation("........./........./...t...../..b....../b...t b").unwrap();
+    assert_eq!(board.get_distance_to_nearest_enemy(36), Some(3));
+    assert_eq!(board.get_pawn_path(36, 40), Some(vec![36, 28, 30, 32, 34, 40]));
+    assert_eq!(board.get_pawn_distances(36)[29], None);
+    assert_eq!(board.get_pawn_distances(36)[21], Some(3));
+    assert_eq!(board.get_pawn_path(36, 29), None);
+
+    let blocked = BoardState::from_notation("........./........./........./.b......./b...t b").unwrap();
+    assert_eq!(blocked.get_distance_to_nearest_enemy(36), None);
+    assert_eq!(blocked.get_pawn_path(36, 40), None);
+    assert_eq!(blocked.get_empty_regions(), vec![(0..NUMBER_OF_TILES).filter(|tile_index| ![28, 36, 40].contains(tile_index)).collect::<Vec<usize>>()]);
+}
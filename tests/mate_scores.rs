@@ -0,0 +1,49 @@
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::evaluation::*;
+use octo_chess::tablebase::*;
+
+// Top to move, its only pawn on tile 12 next to the only bottom pawn on tile 14.
+const LAST_PAWN_POSITION: &str = "........./...t.b.../........./........./..... t";
+const LAST_PAWN_CAPTURE: (usize, usize) = (12, 14);
+
+fn get_small_tablebase() -> Tablebase {
+    let mut tablebase = Tablebase::default();
+    let table = Table::generate(1, 1, &tablebase, |_, _| {}).unwrap();
+    tablebase.add_table(table);
+    tablebase
+}
+
+#[test]
+fn tablebase_results_count_the_plies_from_the_root() {
+    assert_eq!(TablebaseResult::Win(3).get_score(2), MATE_SCORE - 5);
+    assert_eq!(TablebaseResult::Loss(4).get_score(1), -(MATE_SCORE - 5));
+    assert_eq!(TablebaseResult::Draw.get_score(1), 0);
+}
+
+#[test]
+fn eliminating_scores_as_a_mate() {
+    let board = BoardState::from_notation(LAST_PAWN_POSITION).unwrap();
+    let weights = EvaluationWeights::default();
+    let mut context = SearchContext::new(&weights, None);
+    let scores = Brain::score_plays(&board, 2, &mut context);
+    assert!(scores.contains(&(LAST_PAWN_CAPTURE, MATE_SCORE - 1)));
+}
+
+#[test]
+fn eliminating_scores_above_a_tablebase_win() {
+    let board = BoardState::from_notation(LAST_PAWN_POSITION).unwrap();
+    let tablebase = get_small_tablebase();
+    let weights = EvaluationWeights::default();
+    let mut context = SearchContext::new(&weights, None);
+    context.tablebase = Some(&tablebase);
+    let scores = Brain::score_plays(&board, 2, &mut context);
+    for (play, score) in scores {
+        match play == LAST_PAWN_CAPTURE {
+            true => assert_eq!(score, MATE_SCORE - 1),
+            false => assert!(score < MATE_SCORE - 1, "{:?}", play),
+        }
+    }
+
+    assert_eq!(Brain::search_alpha_beta(&board, 2, &mut context), Some(LAST_PAWN_CAPTURE));
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+
+// Counts of reference_perft below for the depths 1 to 6, the first position being the starting one.
+const PERFT_REFERENCE: [(&str, &[u64]); 4] = [
+    ("...ttt.../....t..../........./...b.b.../..b.. b", &[10, 100, 1140, 15936, 184512, 2671794]),
+    ("...ttt.../........./.....t.../.....b.../..b.. b", &[7, 118, 861, 14154, 119416, 1946254]),
+    ("...tt..t./........./.....b.../........./..b.. b", &[10, 130, 1144, 13314, 130200, 1537245]),
+    ("....t..../........./.......b./........./..... t", &[2, 14, 98, 462, 2144, 11864]),
+];
+// Depths counted again by the test with reference_perft, the last one taking seconds more in a debug build.
+const REFERENCE_DEPTH: u32 = 5;
+
+// A move generation written from the shape of the board only, without BoardState nor Grid. Tiles are placed by
+// column and by half row from the top : squares, the quad tiles, at even ones, and octagons, the octo tiles, at odd ones.
+// An octagon shares a side with the 4 octagons in line with it and with the 4 squares on its corners.
+type ReferenceTiles = BTreeMap<(i32, i32), char>;
+
+fn is_reference_tile(tile: (i32, i32)) -> bool {
+    (0..9).contains(&tile.0) && (0..9).contains(&tile.1) && tile.0 % 2 == tile.1 % 2
+}
+
+fn get_reference_neighbours(tile: (i32, i32)) -> Vec<(i32, i32)> {
+    let steps: &[(i32, i32)] = match tile.0 % 2 {
+        0 => &[(-1, -1), (1, -1), (-1, 1), (1, 1)],
+        _ => &[(-1, -1), (1, -1), (-1, 1), (1, 1), (-2, 0), (2, 0), (0, -2), (0, 2)],
+    };
+
+    steps.iter().map(|step| (tile.0 + step.0, tile.1 + step.1)).filter(|neighbour| is_reference_tile(*neighbour)).collect()
+}
+
+// Rows of 9 tiles alternate squares and octagons, the last row only has its 5 squares.
+fn parse_reference(notation: &str) -> (ReferenceTiles, char) {
+    let (rows, side) = notation.split_once(' ').unwrap();
+    let mut tiles = ReferenceTiles::new();
+    for (row, characters) in rows.split('/').enumerate() {
+        let step = match characters.len() { 9 => 1, _ => 2 };
+        for (column, character) in characters.chars().enumerate() {
+            let column = column as i32 * step;
+            if character != '.' {
+                tiles.insert((column, row as i32 * 2 + column % 2), character);
+            }
+        }
+    }
+
+    (tiles, side.chars().next().unwrap())
+}
+
+fn reference_perft(tiles: &ReferenceTiles, side: char, depth: u32) -> u64 {
+    let other_side = match side { 't' => 'b', _ => 't' };
+    if depth == 0 {
+        return 1;
+    }
+
+    if !tiles.values().any(|pawn| *pawn == side) || !tiles.values().any(|pawn| *pawn == other_side) {
+        return 0;
+    }
+
+    let mut count = 0;
+    for (tile, _) in tiles.iter().filter(|(_, pawn)| **pawn == side) {
+        for neighbour in get_reference_neighbours(*tile) {
+            if tiles.get(&neighbour) != Some(&side) {
+                let mut next_tiles = tiles.clone();
+                next_tiles.remove(tile);
+                next_tiles.insert(neighbour, side);
+                count += reference_perft(&next_tiles, other_side, depth - 1);
+            }
+        }
+    }
+
+    count
+}
+
+// Counted by hand : the two front bottom pawns are on octo tiles with 5 free neighbours each, the one behind them
+// has none, and the top side answers with the same 10 plays whatever the first one, the sides being too far apart.
+#[test]
+fn perft_matches_hand_counts() {
+    let board = BoardState::starting_position();
+    assert_eq!(Brain::perft(&board, 1), 10);
+    assert_eq!(Brain::perft(&board, 2), 100);
+}
+
+#[test]
+fn reference_counts_match_the_reference_generator() {
+    for (notation, counts) in PERFT_REFERENCE {
+        let (tiles, side) = parse_reference(notation);
+        for depth in 1..=REFERENCE_DEPTH {
+            assert_eq!(reference_perft(&tiles, side, depth), counts[depth as usize - 1], "{0} depth {1}", notation, depth);
+        }
+    }
+}
+
+#[test]
+fn perft_matches_reference_counts() {
+    for (notation, counts) in PERFT_REFERENCE {
+        let board = BoardState::from_notation(notation).unwrap();
+        for (depth, expected) in counts.iter().enumerate() {
+            assert_eq!(Brain::perft(&board, depth as u32 + 1), *expected, "{0} depth {1}", notation, depth + 1);
+        }
+    }
+}
+
+#[test]
+fn perft_divide_adds_up() {
+    let board = BoardState::starting_position();
+    let total: u64 = Brain::perft_divide(&board, 4).iter().map(|(_, count)| count).sum();
+    assert_eq!(total, Brain::perft(&board, 4));
+}
@@ -0,0 +1,22 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use octo_chess::board::*;
+use octo_chess::self_play::*;
+
+#[test]
+fn random_opening_stops_before_the_end_of_the_game() {
+    // With a single pawn on each side, so many plies end nearly every random line.
+    let board = BoardState::from_notation("........./...t.b.../........./........./..... t").unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..20 {
+        assert!(play_random_opening(&board, 1000, &mut rng).get_winner().is_none());
+    }
+}
+
+#[test]
+fn random_opening_keeps_a_finished_board() {
+    let board = BoardState::from_notation("........./...t...../........./........./..... b").unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(play_random_opening(&board, 4, &mut rng).to_notation(), board.to_notation());
+}
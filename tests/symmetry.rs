@@ -0,0 +1,69 @@
+use std::collections::BTreeSet;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::self_play::*;
+use octo_chess::symmetry::*;
+
+// Positions from seeded random openings of every length up to 30 plies.
+fn get_positions() -> Vec<BoardState> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..30).map(|plies| play_random_opening(&BoardState::starting_position(), plies, &mut rng)).collect()
+}
+
+fn get_plays(board: &BoardState) -> BTreeSet<(usize, usize)> {
+    Brain::find_all_plays(board, board.current_player).into_iter().collect()
+}
+
+#[test]
+fn transforms_move_the_tiles_by_hand() {
+    let mirror = BoardTransform { mirror: true, swap_sides: false };
+    let swap_sides = BoardTransform { mirror: false, swap_sides: true };
+    assert_eq!(mirror.apply_to_tile(0), 8);
+    assert_eq!(mirror.apply_to_tile(37), 39);
+    assert_eq!(mirror.apply_to_tile(22), 22);
+    // The first row of quad tiles becomes the last one, octo tiles stay between the same rows.
+    assert_eq!(swap_sides.apply_to_tile(0), 36);
+    assert_eq!(swap_sides.apply_to_tile(39), 6);
+    assert_eq!(swap_sides.apply_to_tile(1), 28);
+    assert_eq!(swap_sides.apply_to_play((3, 13)), (30, 31));
+}
+
+#[test]
+fn transforms_applied_twice_give_the_position_back() {
+    for board in get_positions() {
+        for transform in BoardTransform::all() {
+            let transformed = board.transform(transform);
+            assert!(transformed.check_invariants().is_ok());
+            assert_eq!(transformed.transform(transform.inverse()).to_notation(), board.to_notation(), "{:?}", transform);
+        }
+    }
+}
+
+#[test]
+fn plays_of_a_transformed_position_map_back_to_the_plays() {
+    for board in get_positions() {
+        for transform in BoardTransform::all() {
+            let transformed = board.transform(transform);
+            let plays: BTreeSet<(usize, usize)> = get_plays(&transformed).into_iter()
+                .map(|play| transform.inverse().apply_to_play(play))
+                .collect();
+            assert_eq!(plays, get_plays(&board), "{0} {1:?}", board.to_notation(), transform);
+        }
+    }
+}
+
+#[test]
+fn symmetric_positions_have_the_same_canonical_position() {
+    for board in get_positions() {
+        let (canonical, transform) = board.canonical();
+        assert_eq!(board.transform(transform).to_notation(), canonical.to_notation());
+        for other_transform in BoardTransform::all() {
+            let (other_canonical, _) = board.transform(other_transform).canonical();
+            assert_eq!(other_canonical.get_key(), canonical.get_key(), "{0} {1:?}", board.to_notation(), other_transform);
+        }
+    }
+}
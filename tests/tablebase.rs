@@ -0,0 +1,46 @@
+use octo_chess::tablebase::*;
+
+fn get_table() -> Table {
+    Table::generate(1, 1, &Tablebase::default(), |_, _| {}).unwrap()
+}
+
+// The side to move can only eliminate the other one on its own plies.
+#[test]
+fn wins_take_an_odd_number_of_plies() {
+    let table = get_table();
+    for index in 0..Table::get_size(1, 1) {
+        match table.get(index) {
+            TablebaseResult::Win(plies) => assert_eq!(plies % 2, 1, "{}", index),
+            TablebaseResult::Loss(plies) => assert_eq!(plies % 2, 0, "{}", index),
+            TablebaseResult::Draw => {},
+        }
+    }
+}
+
+#[test]
+fn best_plays_keep_the_result() {
+    let table = get_table();
+    let mut tablebase = Tablebase::default();
+    tablebase.add_table(get_table());
+    for index in 0..Table::get_size(1, 1) {
+        let board = Table::get_position(1, 1, index);
+        let result = table.get(index);
+        assert_eq!(tablebase.probe(&board), Some(result));
+        if let Some(((source, destination), best_result)) = tablebase.get_best_play(&board) {
+            assert_eq!(best_result, result, "{}", index);
+            let next_result = tablebase.probe(&board.make_move(source, destination)).unwrap();
+            assert_eq!(next_result.previous_ply(), result, "{}", index);
+        }
+    }
+}
+
+#[test]
+fn tables_read_back_from_their_bytes() {
+    let table = get_table();
+    let read_table = Table::from_bytes(&table.to_bytes()).unwrap();
+    for index in 0..Table::get_size(1, 1) {
+        assert_eq!(read_table.get(index), table.get(index), "{}", index);
+    }
+
+    assert!(Table::from_bytes(b"OCTB\x01\x01").is_err());
+}
@@ -0,0 +1,42 @@
+use octo_chess::board::*;
+use octo_chess::brain::*;
+use octo_chess::grid::*;
+use octo_chess::evaluation::*;
+
+// Positions where a search stopping at the depth walks into a recapture, with the depth and the play losing a pawn.
+// Found by comparing the shallow search with a deeper one.
+const RECAPTURE_POSITIONS: [(&str, u32, &str); 8] = [
+    ("....tt.../....t.t../.....b.../...b...../..b.. b", 1, "F3-F5"),
+    ("....tt.../....tbt../........./...b...../..b.. t", 1, "E8-D7"),
+    ("....t..../....ttt../........./...b.b.../..... b", 1, "F1-F3"),
+    ("....tt.../....t..../.b..t..../.....b.../..... b", 1, "B3-D3"),
+    ("...tt..../........./...bt.t../...b...../..... b", 1, "D3-F3"),
+    ("..t.t..../........./....tbt../........./.b... t", 1, "C8-D7"),
+    ("..t.t..../........./.t....b../........./.b... b", 1, "C0-B1"),
+    ("..t..t.../........./........./t......../.b.b. t", 1, "A2-B1"),
+];
+
+fn search(notation: &str, depth: u32, quiescence: bool) -> String {
+    let board = BoardState::from_notation(notation).unwrap();
+    let weights = EvaluationWeights::default();
+    let mut context = SearchContext::new(&weights, None);
+    context.quiescence = quiescence;
+    Grid::get_play_name(Brain::search_alpha_beta(&board, depth, &mut context).unwrap())
+}
+
+#[test]
+fn quiescence_avoids_the_recaptures() {
+    for (notation, depth, losing_play) in RECAPTURE_POSITIONS {
+        assert_ne!(search(notation, depth, true), losing_play, "{}", notation);
+    }
+}
+
+#[test]
+fn game_config_searches_the_captures() {
+    let config = AiConfig::new(SearchAlgorithm::AlphaBeta, EvaluationProfile::default());
+    for (notation, _, losing_play) in RECAPTURE_POSITIONS {
+        let board = BoardState::from_notation(notation).unwrap();
+        let play = config.search_best_play(&board).map(Grid::get_play_name);
+        assert_ne!(play.as_deref(), Some(losing_play), "{}", notation);
+    }
+}
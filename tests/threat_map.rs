@@ -0,0 +1,41 @@
+use octo_chess::game::*;
+use octo_chess::board::*;
+use octo_chess::threat_map::*;
+
+// A top pawn on tile 12 next to a bottom pawn on tile 14, which the bottom pawn on tile 22 protects.
+// Both bottom pawns can capture the top one, which no other top pawn protects.
+const POSITION: &str = "........./...t.b.../....b..../........./..... b";
+
+fn get_threat_map() -> (BoardState, ThreatMap) {
+    let board = BoardState::from_notation(POSITION).unwrap();
+    let threat_map = board.get_threat_map();
+    (board, threat_map)
+}
+
+#[test]
+fn attackers_are_the_pawns_moving_onto_the_tile() {
+    let (_, threat_map) = get_threat_map();
+    assert_eq!(threat_map.get_attackers(14, PlayerSide::Top), &[12]);
+    assert_eq!(threat_map.get_attackers(12, PlayerSide::Bottom), &[14, 22]);
+    assert_eq!(threat_map.get_attackers(13, PlayerSide::Top), &[12]);
+    assert_eq!(threat_map.get_attackers(13, PlayerSide::Bottom), &[14]);
+    assert_eq!(threat_map.get_attackers(23, PlayerSide::Bottom), &[14, 22]);
+    assert!(threat_map.get_attackers(23, PlayerSide::Top).is_empty());
+    assert!(threat_map.get_attackers(0, PlayerSide::Bottom).is_empty());
+}
+
+#[test]
+fn defended_tiles_are_the_tiles_reached_in_one_move() {
+    let (_, threat_map) = get_threat_map();
+    assert_eq!(threat_map.get_defended_tiles(PlayerSide::Top), vec![3, 10, 11, 13, 14, 20, 21, 22]);
+    assert!(threat_map.is_defended(14, PlayerSide::Bottom));
+    assert!(threat_map.is_defended(22, PlayerSide::Bottom));
+    assert!(!threat_map.is_defended(12, PlayerSide::Top));
+}
+
+#[test]
+fn hanging_pawns_are_attacked_and_not_defended() {
+    let (board, threat_map) = get_threat_map();
+    assert_eq!(threat_map.get_hanging_pawns(&board, PlayerSide::Top), vec![12]);
+    assert!(threat_map.get_hanging_pawns(&board, PlayerSide::Bottom).is_empty());
+}